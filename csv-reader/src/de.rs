@@ -0,0 +1,355 @@
+use std::fmt;
+use std::iter::Zip;
+use std::slice::Iter;
+use std::str::FromStr;
+
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{
+    self, Deserialize, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+
+use crate::{CsvError, Record};
+
+#[derive(Debug)]
+struct DeError {
+    field: Option<String>,
+    message: String,
+}
+
+impl fmt::Display for DeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for DeError {}
+
+impl de::Error for DeError {
+    fn custom<T: fmt::Display>(message: T) -> DeError {
+        DeError {
+            field: None,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Deserializes `record` as a map from header names to fields, or as a sequence of fields
+/// for tuples. Errors name the record's line and, where known, the header of the field
+/// that failed.
+pub(crate) fn from_record<'de, T: Deserialize<'de>>(record: &'de Record) -> Result<T, CsvError> {
+    T::deserialize(RecordDeserializer(record)).map_err(|error| CsvError::Deserialize {
+        line: record.line(),
+        field: error.field,
+        message: error.message,
+    })
+}
+
+struct RecordDeserializer<'de>(&'de Record);
+
+impl<'de> RecordDeserializer<'de> {
+    fn fields(&self) -> Fields<'de> {
+        Fields {
+            iter: self.0.headers().iter().zip(self.0.fields().iter()),
+            current: None,
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for RecordDeserializer<'de> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_map(self.fields())
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_seq(self.fields())
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, DeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct enum identifier ignored_any
+    }
+}
+
+struct Fields<'de> {
+    iter: Zip<Iter<'de, String>, Iter<'de, String>>,
+    current: Option<(&'de str, &'de str)>,
+}
+
+impl<'de> Fields<'de> {
+    fn deserialize_current<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<T::Value, DeError> {
+        let (header, field) = self
+            .current
+            .take()
+            .ok_or_else(|| de::Error::custom("a field was asked for before its header"))?;
+        seed.deserialize(FieldDeserializer(field))
+            .map_err(|error| DeError {
+                field: Some(header.to_string()),
+                ..error
+            })
+    }
+}
+
+impl<'de> MapAccess<'de> for Fields<'de> {
+    type Error = DeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, DeError> {
+        match self.iter.next() {
+            Some((header, field)) => {
+                self.current = Some((header, field));
+                seed.deserialize(BorrowedStrDeserializer::new(header))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, DeError> {
+        self.deserialize_current(seed)
+    }
+}
+
+impl<'de> SeqAccess<'de> for Fields<'de> {
+    type Error = DeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, DeError> {
+        match self.iter.next() {
+            Some((header, field)) => {
+                self.current = Some((header, field));
+                self.deserialize_current(seed).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// A single field. Empty fields are `None` for options, booleans accept any casing of
+/// `true` and `false` (the MLB export uses `TRUE` and `FALSE`) and numbers may be padded
+/// with whitespace.
+struct FieldDeserializer<'de>(&'de str);
+
+impl<'de> FieldDeserializer<'de> {
+    fn parse<T>(&self) -> Result<T, DeError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.0.trim().parse().map_err(|error| {
+            de::Error::custom(format!(
+                "expected {} but found {:?}: {}",
+                std::any::type_name::<T>(),
+                self.0,
+                error
+            ))
+        })
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for FieldDeserializer<'de> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_borrowed_str(self.0)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        match self.0.trim().to_lowercase().as_str() {
+            "true" => visitor.visit_bool(true),
+            "false" => visitor.visit_bool(false),
+            _ => Err(de::Error::custom(format!(
+                "expected bool but found {:?}",
+                self.0
+            ))),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_enum(self.0.into_deserializer())
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 str string bytes byte_buf unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct ScheduleRow<'a> {
+        #[serde(rename = "START DATE")]
+        start_date: &'a str,
+        #[serde(rename = "SUBJECT")]
+        subject: String,
+        #[serde(rename = "DESCRIPTION")]
+        description: Option<String>,
+        #[serde(rename = "REMINDER ON")]
+        reminder_on: bool,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename_all = "UPPERCASE")]
+    enum ShowTimeAs {
+        Free,
+        Busy,
+    }
+
+    #[test]
+    fn it_deserializes_records_into_structs_and_tuples() {
+        let records = crate::read_records(
+            "START DATE,SUBJECT,LOCATION,DESCRIPTION,REMINDER ON,SHOWTIMEAS BUSY\r\n03/28/19,Red Sox at Mariners,T-Mobile Park - Seattle,,TRUE,BUSY\r\n",
+        );
+
+        assert_eq!(
+            Ok(ScheduleRow {
+                start_date: "03/28/19",
+                subject: "Red Sox at Mariners".to_string(),
+                description: None,
+                reminder_on: true,
+            }),
+            records[0].deserialize::<ScheduleRow>()
+        );
+        assert_eq!(
+            Ok((
+                "03/28/19".to_string(),
+                "Red Sox at Mariners".to_string(),
+                "T-Mobile Park - Seattle".to_string(),
+                None,
+                true,
+                ShowTimeAs::Busy
+            )),
+            records[0].deserialize::<(String, String, String, Option<String>, bool, ShowTimeAs)>()
+        );
+    }
+
+    #[test]
+    fn it_reports_mismatches_per_record() {
+        #[derive(Debug, Deserialize)]
+        struct Attendance {
+            #[serde(rename = "ATTENDANCE")]
+            _attendance: u32,
+        }
+
+        let results = crate::Reader::new(
+            "SUBJECT,ATTENDANCE\nRed Sox at Mariners,45000\nRays at Mariners,sold out\nTwins at Mariners\n"
+                .as_bytes(),
+        )
+        .records()
+        .deserialize::<Attendance>()
+        .map(|result| result.err())
+        .collect::<Vec<Option<CsvError>>>();
+
+        assert_eq!(
+            vec![
+                None,
+                Some(CsvError::Deserialize {
+                    line: 3,
+                    field: Some("ATTENDANCE".to_string()),
+                    message: "expected u32 but found \"sold out\": invalid digit found in string"
+                        .to_string()
+                }),
+                Some(CsvError::RaggedRow {
+                    line: 4,
+                    column: 18,
+                    expected: 2,
+                    found: 1
+                }),
+            ],
+            results
+        );
+
+        let missing = crate::read_records("SUBJECT\nRed Sox at Mariners")[0]
+            .deserialize::<Attendance>()
+            .unwrap_err();
+        assert_eq!("line 2: missing field `ATTENDANCE`", missing.to_string());
+    }
+}
@@ -0,0 +1,143 @@
+use std::str::from_utf8;
+
+pub(crate) const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+const DELIMITERS: [char; 4] = [',', ';', '\t', '|'];
+
+/// How the bytes of a schedule export map to text. Excel likes to save as Windows-1252.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Encoding {
+    Utf8,
+    Windows1252,
+    /// UTF-8, except that a line which isn't is read as Windows-1252. For documents whose
+    /// encoding was only guessed from their start.
+    Utf8OrWindows1252,
+}
+
+/// The flavour of CSV a document is written in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dialect {
+    pub delimiter: char,
+    pub encoding: Encoding,
+}
+
+impl Default for Dialect {
+    fn default() -> Dialect {
+        Dialect {
+            delimiter: ',',
+            encoding: Encoding::Utf8,
+        }
+    }
+}
+
+impl Dialect {
+    /// Guesses the dialect from the start of a document. The delimiter is whichever of
+    /// `,` `;` tab or `|` appears most often outside quotes on the first line, preferring
+    /// them in that order on a tie, and the encoding is Windows-1252 if the sample is not
+    /// valid UTF-8.
+    pub fn sniff(sample: &[u8]) -> Dialect {
+        let sample = sample.strip_prefix(UTF8_BOM).unwrap_or(sample);
+
+        let encoding = match from_utf8(sample) {
+            Ok(_) => Encoding::Utf8,
+            Err(error) if error.error_len().is_none() => Encoding::Utf8,
+            Err(_) => Encoding::Windows1252,
+        };
+
+        let first_line = sample.split(|b| *b == b'\n').next().unwrap_or(&[]);
+        let mut counts = [0; DELIMITERS.len()];
+        let mut quoted = false;
+        for b in first_line {
+            match *b as char {
+                '"' => quoted = !quoted,
+                c if !quoted => {
+                    if let Some(index) = DELIMITERS.iter().position(|d| *d == c) {
+                        counts[index] += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let delimiter = counts
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            .fold(
+                None,
+                |best: Option<(usize, usize)>, (index, count)| match best {
+                    Some((_, best_count)) if best_count >= *count => best,
+                    _ => Some((index, *count)),
+                },
+            )
+            .map(|(index, _)| DELIMITERS[index])
+            .unwrap_or(',');
+
+        Dialect {
+            delimiter,
+            encoding,
+        }
+    }
+
+    /// Like `sniff`, but assumes the document is UTF-8.
+    pub fn sniff_delimiter(sample: &[u8]) -> Dialect {
+        Dialect {
+            encoding: Encoding::Utf8,
+            ..Dialect::sniff(sample)
+        }
+    }
+}
+
+/// Windows-1252 only differs from Latin-1 in 0x80..=0x9F. The five bytes it leaves
+/// undefined are passed through as the C1 controls they are in Latin-1.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ',
+];
+
+pub(crate) fn windows_1252_char(b: u8) -> char {
+    match b {
+        0x80..=0x9F => WINDOWS_1252_HIGH[(b - 0x80) as usize],
+        _ => b as char,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_detects_delimiters_outside_quotes() {
+        assert_eq!(
+            ',',
+            Dialect::sniff(b"START DATE,START TIME,SUBJECT\r\n").delimiter
+        );
+        assert_eq!(
+            ';',
+            Dialect::sniff(b"START DATE;\"START, TIME\";SUBJECT").delimiter
+        );
+        assert_eq!(
+            '\t',
+            Dialect::sniff(b"\xEF\xBB\xBFSTART DATE\tSTART TIME\n1,2\t3").delimiter
+        );
+        assert_eq!(',', Dialect::sniff(b"SUBJECT\n").delimiter);
+    }
+
+    #[test]
+    fn it_detects_legacy_encodings() {
+        assert_eq!(
+            Encoding::Utf8,
+            Dialect::sniff("SUBJECT\nPeña at Mariners".as_bytes()).encoding
+        );
+        assert_eq!(
+            Encoding::Utf8,
+            Dialect::sniff(&"SUBJECT\nPeña".as_bytes()[..11]).encoding
+        );
+        assert_eq!(
+            Encoding::Windows1252,
+            Dialect::sniff(b"SUBJECT\nPe\xF1a at Mariners \x96 Game 1").encoding
+        );
+        assert_eq!('–', windows_1252_char(0x96));
+        assert_eq!('ñ', windows_1252_char(0xF1));
+    }
+}
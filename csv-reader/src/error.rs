@@ -0,0 +1,68 @@
+use std::fmt;
+
+/// Why a CSV document could not be read. Lines and columns are 1-based and count
+/// characters, so they line up with what an editor shows.
+#[derive(Debug, PartialEq)]
+pub enum CsvError {
+    UnterminatedQuote {
+        line: usize,
+        column: usize,
+    },
+    RaggedRow {
+        line: usize,
+        column: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidUtf8 {
+        line: usize,
+        column: usize,
+    },
+    Io {
+        line: usize,
+        error: String,
+    },
+    Deserialize {
+        line: usize,
+        field: Option<String>,
+        message: String,
+    },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvError::UnterminatedQuote { line, column } => write!(
+                f,
+                "line {}, column {}: quoted field is never closed",
+                line, column
+            ),
+            CsvError::RaggedRow {
+                line,
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {}, column {}: expected {} fields but found {}",
+                line, column, expected, found
+            ),
+            CsvError::InvalidUtf8 { line, column } => {
+                write!(f, "line {}, column {}: invalid UTF-8", line, column)
+            }
+            CsvError::Io { line, error } => write!(f, "line {}: {}", line, error),
+            CsvError::Deserialize {
+                line,
+                field: Some(field),
+                message,
+            } => write!(f, "line {}, field {:?}: {}", line, field, message),
+            CsvError::Deserialize {
+                line,
+                field: None,
+                message,
+            } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
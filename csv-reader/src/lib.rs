@@ -1,7 +1,36 @@
-pub fn read_rows(contents: &str) -> Vec<Vec<&str>> {
-    let rows: Vec<&str> = contents.lines().collect();
-    rows.into_iter()
-        .map(|row: &str| row.split(',').collect())
+mod de;
+pub mod dialect;
+pub mod error;
+pub mod reader;
+pub mod record;
+mod tokenizer;
+pub mod writer;
+
+pub use dialect::{Dialect, Encoding};
+pub use error::CsvError;
+pub use reader::{Reader, Records};
+pub use record::{read_records, try_read_records, Record};
+pub use writer::Writer;
+
+/// Splits `contents` into rows of fields following RFC 4180: a field may be wrapped in
+/// double quotes, in which case it can contain commas, line breaks and `""` escaped quotes.
+/// Rows may end in CRLF or LF and blank lines are skipped. The delimiter is sniffed (see
+/// `Dialect::sniff`) and a leading byte order mark is dropped. A trailing row whose quote
+/// is never closed is dropped; use `try_read_rows` to find out about it.
+pub fn read_rows(contents: &str) -> Vec<Vec<String>> {
+    tokenizer::tokenize(contents.as_bytes())
+        .into_iter()
+        .filter_map(Result::ok)
+        .map(|row| row.fields)
+        .collect()
+}
+
+/// Like `read_rows`, but stops at the first unterminated quote, invalid UTF-8 sequence or
+/// row whose width differs from the first row's. See `Reader` to stream rows instead, or
+/// `Reader::sniffed` to also accept legacy encodings.
+pub fn try_read_rows(contents: &[u8]) -> Result<Vec<Vec<String>>, CsvError> {
+    Reader::with_dialect(contents, Dialect::sniff_delimiter(contents))
+        .to_end()
         .collect()
 }
 
@@ -25,4 +54,105 @@ mod tests {
             read_rows("header,column_name,wtf\r\n1,2,3\r\n4,5,6")
         );
     }
+
+    #[test]
+    fn it_keeps_commas_inside_quoted_fields() {
+        assert_eq!(
+            vec![vec![
+                "04/13/19",
+                "06:10 PM",
+                "Astros at Mariners",
+                "Local TV: ROOTNW, FS1 ----- Local Radio: 710 ESPN",
+                "04/13/19"
+            ]],
+            read_rows(
+                "04/13/19,06:10 PM,Astros at Mariners,\"Local TV: ROOTNW, FS1 ----- Local Radio: 710 ESPN\",04/13/19\r\n"
+            )
+        );
+    }
+
+    #[test]
+    fn it_unescapes_doubled_quotes_and_keeps_empty_quoted_fields() {
+        assert_eq!(
+            vec![vec![
+                "Angels at Mariners",
+                "",
+                "\"Turn Ahead the Clock\" night",
+                ""
+            ]],
+            read_rows("Angels at Mariners,\"\",\"\"\"Turn Ahead the Clock\"\" night\",")
+        );
+    }
+
+    #[test]
+    fn it_handles_lf_endings_blank_lines_and_newlines_inside_quotes() {
+        assert_eq!(
+            vec![
+                vec!["SUBJECT", "DESCRIPTION"],
+                vec!["Reds at Mariners", "Local TV: ROOTNW\r\nLocal Radio: 710 ESPN"],
+                vec!["Rays at Mariners", "Local TV: ROOTNW\nLocal Radio: 710 ESPN"]
+            ],
+            read_rows(
+                "SUBJECT,DESCRIPTION\n\n\"Reds at Mariners\",\"Local TV: ROOTNW\r\nLocal Radio: 710 ESPN\"\nRays at Mariners,\"Local TV: ROOTNW\nLocal Radio: 710 ESPN\"\n"
+            )
+        );
+    }
+    #[test]
+    fn it_reports_unterminated_quotes_where_they_open() {
+        assert_eq!(
+            Err(CsvError::UnterminatedQuote {
+                line: 2,
+                column: 25
+            }),
+            try_read_rows(
+                b"SUBJECT,LOCATION,DESCRIPTION\r\nReds at Mariners,Peoria,\"Local TV: ROOTNW\r\n"
+            )
+        );
+    }
+
+    #[test]
+    fn it_reports_ragged_rows() {
+        assert_eq!(
+            Err(CsvError::RaggedRow {
+                line: 4,
+                column: 7,
+                expected: 2,
+                found: 3
+            }),
+            try_read_rows(b"A,B\n\"1\n2\",3\n4,\"5\",6\n")
+        );
+        assert_eq!(
+            Err(CsvError::RaggedRow {
+                line: 2,
+                column: 10,
+                expected: 3,
+                found: 2
+            }),
+            try_read_rows(b"A,B,C\n03/28/19,")
+        );
+    }
+
+    #[test]
+    fn it_reports_invalid_utf8() {
+        let error =
+            try_read_rows(b"SUBJECT\nRed Sox at Mariners\nD-backs at Mari\xffners\n").unwrap_err();
+        assert_eq!(
+            CsvError::InvalidUtf8 {
+                line: 3,
+                column: 16
+            },
+            error
+        );
+        assert_eq!("line 3, column 16: invalid UTF-8", error.to_string());
+    }
+    #[test]
+    fn it_drops_the_bom_and_splits_tabs() {
+        assert_eq!(
+            vec![
+                vec!["START DATE", "START TIME"],
+                vec!["03/28/19", "04:10 PM"]
+            ],
+            read_rows("\u{FEFF}START DATE\tSTART TIME\r\n03/28/19\t04:10 PM\r\n")
+        );
+    }
 }
@@ -0,0 +1,382 @@
+use std::io::BufRead;
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+
+use crate::record::header_row;
+use crate::tokenizer::{Row, Tokenizer};
+use crate::{CsvError, Dialect, Encoding, Record};
+
+/// Reads rows one at a time from any `BufRead`, such as a `BufReader<File>` or a locked
+/// stdin. The line buffer is reused between rows, so memory use is bounded by the longest
+/// row rather than the whole document.
+///
+/// Every row must be as wide as the first one. Errors are yielded in place of the row
+/// that caused them and reading carries on with the next row. Once the source runs dry
+/// the iterator returns `None`, but calling `next` again picks up anything appended since.
+/// A last line without its terminator, or with a quote still open, is held back in case
+/// the rest of it is still being written; `finish` flushes it, or `to_end` has the reader
+/// do so itself when the source runs dry.
+pub struct Reader<R> {
+    source: R,
+    tokenizer: Tokenizer,
+    /// The line being read, which is only handed to the tokenizer once it is complete.
+    buffer: Vec<u8>,
+    expected_width: Option<usize>,
+    flush_when_dry: bool,
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Reads comma separated UTF-8.
+    pub fn new(source: R) -> Reader<R> {
+        Reader::with_dialect(source, Dialect::default())
+    }
+
+    pub fn with_dialect(source: R, dialect: Dialect) -> Reader<R> {
+        Reader {
+            source,
+            tokenizer: Tokenizer::new(dialect),
+            buffer: vec![],
+            expected_width: None,
+            flush_when_dry: false,
+        }
+    }
+
+    /// Treats the source running dry as the end of the document, for sources that are
+    /// read whole rather than followed as they grow.
+    pub fn to_end(mut self) -> Reader<R> {
+        self.flush_when_dry = true;
+        self
+    }
+
+    /// Flushes the last line when it has no terminator, reporting a quote that was never
+    /// closed. Reading can carry on afterwards, starting from a new row.
+    pub fn finish(&mut self) -> Option<Result<Vec<String>, CsvError>> {
+        self.finish_checked_row()
+            .map(|row| row.map(|row| row.fields))
+    }
+
+    /// Picks the dialect by sniffing whatever `source` has buffered up front, without
+    /// consuming it. That is only the first few kilobytes, so when they look like UTF-8
+    /// any later line that isn't is read as Windows-1252 rather than rejected. Pass the
+    /// `Dialect` to `with_dialect` instead to hold a document to one encoding.
+    pub fn sniffed(mut source: R) -> Reader<R> {
+        let dialect = source.fill_buf().map(Dialect::sniff).unwrap_or_default();
+        let dialect = match dialect.encoding {
+            Encoding::Utf8 => Dialect {
+                encoding: Encoding::Utf8OrWindows1252,
+                ..dialect
+            },
+            _ => dialect,
+        };
+        Reader::with_dialect(source, dialect)
+    }
+
+    /// Treats the first row as the header and yields the rest as `Record`s.
+    pub fn records(self) -> Records<R> {
+        Records {
+            reader: self,
+            headers: None,
+        }
+    }
+
+    fn next_row(&mut self) -> Option<Result<Row, CsvError>> {
+        loop {
+            match self.source.read_until(b'\n', &mut self.buffer) {
+                Ok(0) if self.flush_when_dry => return self.finish_row(),
+                Ok(0) => return None,
+                Ok(_) if !self.buffer.ends_with(b"\n") => continue,
+                Ok(_) => {
+                    let row = self.tokenizer.feed_line(&self.buffer);
+                    self.buffer.clear();
+                    if let Some(row) = row {
+                        return Some(row);
+                    }
+                }
+                Err(error) => {
+                    return Some(Err(CsvError::Io {
+                        line: self.tokenizer.line() + 1,
+                        error: error.to_string(),
+                    }))
+                }
+            }
+        }
+    }
+
+    fn finish_row(&mut self) -> Option<Result<Row, CsvError>> {
+        let row = if self.buffer.is_empty() {
+            None
+        } else {
+            self.tokenizer.feed_line(&self.buffer)
+        };
+        self.buffer.clear();
+        row.or_else(|| self.tokenizer.finish())
+    }
+
+    fn next_checked_row(&mut self) -> Option<Result<Row, CsvError>> {
+        let row = self.next_row()?;
+        Some(self.check_width(row))
+    }
+
+    fn finish_checked_row(&mut self) -> Option<Result<Row, CsvError>> {
+        let row = self.finish_row()?;
+        Some(self.check_width(row))
+    }
+
+    fn check_width(&mut self, row: Result<Row, CsvError>) -> Result<Row, CsvError> {
+        row.and_then(|row| {
+            row.check_width(&mut self.expected_width)?;
+            Ok(row)
+        })
+    }
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Vec<String>, CsvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_checked_row().map(|row| row.map(|row| row.fields))
+    }
+}
+
+/// The rows of a `Reader` after its header row, keyed by header name.
+pub struct Records<R> {
+    reader: Reader<R>,
+    headers: Option<Arc<Vec<String>>>,
+}
+
+impl<R: BufRead> Records<R> {
+    /// The header row, once the first record has been asked for.
+    pub fn headers(&self) -> Option<&[String]> {
+        self.headers.as_ref().map(|headers| headers.as_slice())
+    }
+
+    /// Flushes the last record like `Reader::finish`.
+    pub fn finish(&mut self) -> Option<Result<Record, CsvError>> {
+        let row = self.reader.finish_checked_row()?;
+        let headers = match &self.headers {
+            Some(headers) => headers.clone(),
+            None => {
+                match row {
+                    Ok(row) => self.headers = Some(header_row(row.fields)),
+                    Err(error) => return Some(Err(error)),
+                }
+                return None;
+            }
+        };
+        Some(row.map(|row| Record::new(row.line, headers, row.fields)))
+    }
+
+    /// Deserializes each record into `T` (see `Record::deserialize`). A record that does
+    /// not fit `T` yields an error without stopping the ones after it.
+    pub fn deserialize<T: DeserializeOwned>(self) -> impl Iterator<Item = Result<T, CsvError>> {
+        self.map(|record| record.and_then(|record| record.deserialize()))
+    }
+}
+
+impl<R: BufRead> Iterator for Records<R> {
+    type Item = Result<Record, CsvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let headers = match &self.headers {
+            Some(headers) => headers.clone(),
+            None => {
+                let headers = match self.reader.next()? {
+                    Ok(fields) => header_row(fields),
+                    Err(error) => return Some(Err(error)),
+                };
+                self.headers = Some(headers.clone());
+                headers
+            }
+        };
+
+        self.reader
+            .next_checked_row()
+            .map(|row| row.map(|row| Record::new(row.line, headers, row.fields)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufReader, Cursor, Read};
+
+    #[test]
+    fn it_streams_rows_from_a_buf_read_and_keeps_going_after_errors() {
+        let source = BufReader::with_capacity(
+            4,
+            "SUBJECT,LOCATION\r\nTwins at Mariners,\"T-Mobile Park - Seattle\"\r\nRays at Mariners\r\nAstros at Mariners,\"T-Mobile Park,\r\nSeattle\"\r\n"
+                .as_bytes(),
+        );
+
+        assert_eq!(
+            vec![
+                Ok(vec!["SUBJECT".to_string(), "LOCATION".to_string()]),
+                Ok(vec![
+                    "Twins at Mariners".to_string(),
+                    "T-Mobile Park - Seattle".to_string()
+                ]),
+                Err(CsvError::RaggedRow {
+                    line: 3,
+                    column: 17,
+                    expected: 2,
+                    found: 1
+                }),
+                Ok(vec![
+                    "Astros at Mariners".to_string(),
+                    "T-Mobile Park,\r\nSeattle".to_string()
+                ]),
+            ],
+            Reader::new(source).collect::<Vec<Result<Vec<String>, CsvError>>>()
+        );
+    }
+
+    #[test]
+    fn it_yields_records_keyed_by_the_header_row() {
+        let mut records = Reader::new(Cursor::new(
+            "START DATE,SUBJECT\n03/28/19,Red Sox at Mariners\n",
+        ))
+        .records();
+        assert_eq!(None, records.headers());
+
+        let record = records.next().unwrap().unwrap();
+        assert_eq!(Some("Red Sox at Mariners"), record.get("SUBJECT"));
+        assert_eq!(2, record.line());
+        assert_eq!(
+            Some(&["START DATE".to_string(), "SUBJECT".to_string()][..]),
+            records.headers()
+        );
+        assert_eq!(None, records.next());
+    }
+
+    #[test]
+    fn it_picks_up_rows_appended_after_reaching_the_end() {
+        let mut reader = Reader::new(Cursor::new(b"SUBJECT\nTwins at Mariners\n".to_vec()));
+        assert_eq!(2, reader.by_ref().count());
+
+        let position = reader.source.position();
+        reader
+            .source
+            .get_mut()
+            .extend_from_slice(b"Rays at Mariners\n");
+        reader.source.set_position(position);
+        assert_eq!(
+            Some(Ok(vec!["Rays at Mariners".to_string()])),
+            reader.next()
+        );
+    }
+
+    #[test]
+    fn it_waits_for_the_rest_of_a_line_being_appended() {
+        let mut reader = Reader::new(Cursor::new(
+            b"SUBJECT,LOCATION\nTwins at Mariners,T-Mob".to_vec(),
+        ));
+        let append = |reader: &mut Reader<Cursor<Vec<u8>>>, bytes: &[u8]| {
+            let position = reader.source.position();
+            reader.source.get_mut().extend_from_slice(bytes);
+            reader.source.set_position(position);
+        };
+
+        assert_eq!(1, reader.by_ref().count());
+        append(&mut reader, b"ile Park\nRays at Mariners,\"T-Mobile Park,");
+        assert_eq!(
+            Some(Ok(vec![
+                "Twins at Mariners".to_string(),
+                "T-Mobile Park".to_string()
+            ])),
+            reader.next()
+        );
+        assert_eq!(None, reader.next());
+
+        append(&mut reader, b" Seattle\"\n");
+        assert_eq!(
+            Some(Ok(vec![
+                "Rays at Mariners".to_string(),
+                "T-Mobile Park, Seattle".to_string()
+            ])),
+            reader.next()
+        );
+
+        append(&mut reader, b"Astros at Mariners,\"T-Mob");
+        assert_eq!(None, reader.next());
+        assert_eq!(
+            Some(Err(CsvError::UnterminatedQuote {
+                line: 4,
+                column: 20
+            })),
+            reader.finish()
+        );
+        assert_eq!(None, reader.finish());
+    }
+
+    #[test]
+    fn it_sniffs_excel_exports() {
+        let source = b"\xEF\xBB\xBFSTART DATE;SUBJECT;LOCATION\r\n03/28/19;Red Sox at Mari\xF1eros;\"T-Mobile Park; Seattle\"\r\n";
+
+        assert_eq!(
+            vec![
+                Ok(vec![
+                    "START DATE".to_string(),
+                    "SUBJECT".to_string(),
+                    "LOCATION".to_string()
+                ]),
+                Ok(vec![
+                    "03/28/19".to_string(),
+                    "Red Sox at Mariñeros".to_string(),
+                    "T-Mobile Park; Seattle".to_string()
+                ]),
+            ],
+            Reader::sniffed(&source[..]).collect::<Vec<Result<Vec<String>, CsvError>>>()
+        );
+        assert_eq!(
+            Some("03/28/19"),
+            Reader::new(&b"\xEF\xBB\xBFSTART DATE\n03/28/19"[..])
+                .to_end()
+                .records()
+                .next()
+                .unwrap()
+                .unwrap()
+                .get("START DATE")
+        );
+    }
+
+    #[test]
+    fn it_falls_back_to_windows_1252_past_the_sniffed_start() {
+        let mut source = b"SUBJECT\n".to_vec();
+        for _ in 0..10 {
+            source.extend_from_slice("Peña at Mariners\n".as_bytes());
+        }
+        source.extend_from_slice(b"Pe\xF1a at Mariners \x96 Game 2\n");
+
+        let rows = Reader::sniffed(BufReader::with_capacity(16, &source[..]))
+            .collect::<Result<Vec<Vec<String>>, CsvError>>()
+            .unwrap();
+        assert_eq!(vec!["Peña at Mariners".to_string()], rows[1]);
+        assert_eq!(vec!["Peña at Mariners – Game 2".to_string()], rows[11]);
+    }
+
+    #[test]
+    fn it_reports_io_errors_with_the_line_they_happened_on() {
+        struct Flaky<'a>(&'a [u8]);
+
+        impl<'a> Read for Flaky<'a> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                if self.0.is_empty() {
+                    Err(std::io::Error::other("disk on fire"))
+                } else {
+                    self.0.read(buf)
+                }
+            }
+        }
+
+        let mut reader = Reader::new(BufReader::new(Flaky(b"SUBJECT\n")));
+        assert_eq!(Some(Ok(vec!["SUBJECT".to_string()])), reader.next());
+        assert_eq!(
+            Some(Err(CsvError::Io {
+                line: 2,
+                error: "disk on fire".to_string()
+            })),
+            reader.next()
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::de::from_record;
+use crate::tokenizer::tokenize;
+use crate::{CsvError, Dialect, Reader};
+
+/// A single CSV row whose fields can be looked up by the header row's column names.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    line: usize,
+    headers: Arc<Vec<String>>,
+    fields: Vec<String>,
+}
+
+impl Record {
+    pub fn new(line: usize, headers: Arc<Vec<String>>, fields: Vec<String>) -> Record {
+        Record {
+            line,
+            headers,
+            fields,
+        }
+    }
+
+    /// Returns the field under `header`, or `None` if there is no such column or this row
+    /// is too short to have it.
+    pub fn get(&self, header: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .position(|h| h == header)
+            .and_then(|index| self.fields.get(index))
+            .map(String::as_str)
+    }
+
+    /// The line of the source document this record starts on.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    /// Deserializes this record into `T`. Structs and maps are filled by header name, so
+    /// fields can be picked out with `#[serde(rename = "START DATE")]`, and tuples and
+    /// sequences are filled in column order.
+    pub fn deserialize<'de, T: Deserialize<'de>>(&'de self) -> Result<T, CsvError> {
+        from_record(self)
+    }
+}
+
+pub(crate) fn header_row(fields: Vec<String>) -> Arc<Vec<String>> {
+    Arc::new(
+        fields
+            .into_iter()
+            .map(|header| header.trim().to_string())
+            .collect(),
+    )
+}
+
+/// Reads `contents` like `read_rows`, treating the first row as the header.
+pub fn read_records(contents: &str) -> Vec<Record> {
+    let mut rows = tokenize(contents.as_bytes())
+        .into_iter()
+        .filter_map(Result::ok);
+    let headers = match rows.next() {
+        Some(row) => header_row(row.fields),
+        None => return vec![],
+    };
+
+    rows.map(|row| Record::new(row.line, headers.clone(), row.fields))
+        .collect()
+}
+
+/// Reads `contents` like `try_read_rows`, treating the first row as the header.
+pub fn try_read_records(contents: &[u8]) -> Result<Vec<Record>, CsvError> {
+    Reader::with_dialect(contents, Dialect::sniff_delimiter(contents))
+        .to_end()
+        .records()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_looks_up_fields_by_header_name() {
+        let records = read_records(
+            "START DATE,START TIME,SUBJECT\r\n03/28/19,04:10 PM,Red Sox at Mariners\r\n03/29/19,07:10 PM",
+        );
+
+        assert_eq!(2, records.len());
+        assert_eq!(Some("04:10 PM"), records[0].get("START TIME"));
+        assert_eq!(Some("Red Sox at Mariners"), records[0].get("SUBJECT"));
+        assert_eq!(None, records[0].get("LOCATION"));
+        assert_eq!(Some("03/29/19"), records[1].get("START DATE"));
+        assert_eq!(None, records[1].get("SUBJECT"));
+    }
+
+    #[test]
+    fn it_does_not_depend_on_column_order() {
+        let records = read_records("SUBJECT, START DATE\nRed Sox at Mariners,03/28/19");
+
+        assert_eq!(Some("03/28/19"), records[0].get("START DATE"));
+        assert_eq!(Vec::<Record>::new(), read_records(""));
+    }
+
+    #[test]
+    fn it_keeps_line_numbers_and_checks_widths_against_the_header() {
+        let records = try_read_records(
+            b"SUBJECT,DESCRIPTION\n\nReds at Mariners,\"ROOTNW\n710 ESPN\"\nRays at Mariners,\"\"",
+        )
+        .unwrap();
+        assert_eq!(
+            vec![3, 5],
+            records.iter().map(Record::line).collect::<Vec<usize>>()
+        );
+
+        assert_eq!(
+            Err(CsvError::RaggedRow {
+                line: 2,
+                column: 18,
+                expected: 1,
+                found: 2
+            }),
+            try_read_records(b"SUBJECT\nReds at Mariners,Peoria")
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use std::mem::take;
+
+use crate::dialect::{windows_1252_char, UTF8_BOM};
+use crate::{CsvError, Dialect, Encoding};
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    StartOfField,
+    Unquoted,
+    Quoted,
+    QuoteInQuoted,
+}
+
+/// A tokenized row along with where it started and where each of its fields begins.
+#[derive(Debug, Default)]
+pub(crate) struct Row {
+    pub line: usize,
+    pub fields: Vec<String>,
+    pub columns: Vec<usize>,
+    pub end_column: usize,
+}
+
+impl Row {
+    /// Checks this row has as many fields as the first row did.
+    pub fn check_width(&self, expected: &mut Option<usize>) -> Result<(), CsvError> {
+        let expected = *expected.get_or_insert(self.fields.len());
+        if self.fields.len() == expected {
+            Ok(())
+        } else {
+            Err(CsvError::RaggedRow {
+                line: self.line,
+                column: self
+                    .columns
+                    .get(expected)
+                    .cloned()
+                    .unwrap_or(self.end_column),
+                expected,
+                found: self.fields.len(),
+            })
+        }
+    }
+}
+
+/// An RFC 4180 state machine that is fed one physical line at a time, so a quoted field
+/// may span several lines.
+pub(crate) struct Tokenizer {
+    dialect: Dialect,
+    state: State,
+    row: Row,
+    field: String,
+    field_column: usize,
+    line: usize,
+    quote_opened_at: (usize, usize),
+    invalid_utf8_at: Option<(usize, usize)>,
+}
+
+impl Tokenizer {
+    pub fn new(dialect: Dialect) -> Tokenizer {
+        Tokenizer {
+            dialect,
+            state: State::StartOfField,
+            row: Row::default(),
+            field: String::new(),
+            field_column: 1,
+            line: 0,
+            quote_opened_at: (0, 0),
+            invalid_utf8_at: None,
+        }
+    }
+
+    /// The number of lines fed so far.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    fn in_progress(&self) -> bool {
+        self.state != State::StartOfField || !self.row.fields.is_empty()
+    }
+
+    /// Consumes one line including its `\n` or `\r\n` terminator, returning the row it
+    /// completes, if any. Blank lines complete nothing and a byte order mark at the very
+    /// start of the document is dropped.
+    pub fn feed_line(&mut self, bytes: &[u8]) -> Option<Result<Row, CsvError>> {
+        self.line += 1;
+        if !self.in_progress() {
+            self.row.line = self.line;
+            self.field_column = 1;
+        }
+
+        let bytes = match self.line {
+            1 => bytes.strip_prefix(UTF8_BOM).unwrap_or(bytes),
+            _ => bytes,
+        };
+        let (content, terminator) = split_terminator(bytes);
+        let mut column = 0;
+        let encoding = match self.dialect.encoding {
+            Encoding::Utf8OrWindows1252 if std::str::from_utf8(content).is_err() => {
+                Encoding::Windows1252
+            }
+            Encoding::Utf8OrWindows1252 => Encoding::Utf8,
+            encoding => encoding,
+        };
+        match encoding {
+            Encoding::Utf8 | Encoding::Utf8OrWindows1252 => {
+                for chunk in content.utf8_chunks() {
+                    for c in chunk.valid().chars() {
+                        column += 1;
+                        self.feed_char(c, column);
+                    }
+                    if !chunk.invalid().is_empty() {
+                        column += 1;
+                        if self.invalid_utf8_at.is_none() {
+                            self.invalid_utf8_at = Some((self.line, column));
+                        }
+                        self.feed_char(char::REPLACEMENT_CHARACTER, column);
+                    }
+                }
+            }
+            Encoding::Windows1252 => {
+                for b in content {
+                    column += 1;
+                    self.feed_char(windows_1252_char(*b), column);
+                }
+            }
+        }
+
+        self.row.end_column = column + 1;
+
+        if self.state == State::Quoted {
+            self.field.push_str(terminator);
+            None
+        } else if terminator.is_empty() || (column == 0 && !self.in_progress()) {
+            None
+        } else {
+            Some(self.finish_row())
+        }
+    }
+
+    /// Flushes whatever is left once the input runs out.
+    pub fn finish(&mut self) -> Option<Result<Row, CsvError>> {
+        if self.state == State::Quoted {
+            let (line, column) = self.quote_opened_at;
+            *self = Tokenizer {
+                line: self.line,
+                ..Tokenizer::new(self.dialect)
+            };
+            Some(Err(CsvError::UnterminatedQuote { line, column }))
+        } else if self.in_progress() {
+            Some(self.finish_row())
+        } else {
+            None
+        }
+    }
+
+    fn feed_char(&mut self, c: char, column: usize) {
+        match (self.state, c) {
+            (State::Quoted, '"') => self.state = State::QuoteInQuoted,
+            (State::Quoted, _) => self.field.push(c),
+            (State::QuoteInQuoted, '"') => {
+                self.field.push('"');
+                self.state = State::Quoted;
+            }
+            (State::StartOfField, '"') => {
+                self.quote_opened_at = (self.line, column);
+                self.state = State::Quoted;
+            }
+            (_, c) if c == self.dialect.delimiter => {
+                self.end_field();
+                self.field_column = column + 1;
+            }
+            _ => {
+                self.field.push(c);
+                self.state = State::Unquoted;
+            }
+        }
+    }
+
+    fn end_field(&mut self) {
+        self.row.fields.push(self.field.clone());
+        self.field.clear();
+        self.row.columns.push(self.field_column);
+        self.state = State::StartOfField;
+    }
+
+    fn finish_row(&mut self) -> Result<Row, CsvError> {
+        self.end_field();
+        let row = take(&mut self.row);
+        match self.invalid_utf8_at.take() {
+            Some((line, column)) => Err(CsvError::InvalidUtf8 { line, column }),
+            None => Ok(row),
+        }
+    }
+}
+
+fn split_terminator(bytes: &[u8]) -> (&[u8], &'static str) {
+    if bytes.ends_with(b"\r\n") {
+        (&bytes[..bytes.len() - 2], "\r\n")
+    } else if bytes.ends_with(b"\n") {
+        (&bytes[..bytes.len() - 1], "\n")
+    } else {
+        (bytes, "")
+    }
+}
+
+/// Tokenizes a whole in-memory UTF-8 document, sniffing its delimiter.
+pub(crate) fn tokenize(contents: &[u8]) -> Vec<Result<Row, CsvError>> {
+    let mut tokenizer = Tokenizer::new(Dialect::sniff_delimiter(contents));
+    let mut rows: Vec<Result<Row, CsvError>> = contents
+        .split_inclusive(|b| *b == b'\n')
+        .filter_map(|line| tokenizer.feed_line(line))
+        .collect();
+    rows.extend(tokenizer.finish());
+    rows
+}
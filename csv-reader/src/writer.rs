@@ -0,0 +1,116 @@
+use std::io::{self, Write};
+
+use crate::Record;
+
+/// Writes RFC 4180 CSV with CRLF line endings. Fields are only quoted when they contain
+/// the delimiter, a quote or a line break, unless their column is marked as always quoted,
+/// which lets exports such as MLB's (which always quotes DESCRIPTION) be reproduced byte
+/// for byte.
+pub struct Writer<W> {
+    sink: W,
+    delimiter: char,
+    always_quoted: Vec<usize>,
+}
+
+impl<W: Write> Writer<W> {
+    pub fn new(sink: W) -> Writer<W> {
+        Writer::with_always_quoted(sink, &[])
+    }
+
+    /// `always_quoted` are the 0-based indexes of columns to quote even when not needed.
+    pub fn with_always_quoted(sink: W, always_quoted: &[usize]) -> Writer<W> {
+        Writer {
+            sink,
+            delimiter: ',',
+            always_quoted: always_quoted.to_vec(),
+        }
+    }
+
+    /// Writes a header row, which is only ever quoted where it needs to be.
+    pub fn write_header<S: AsRef<str>>(&mut self, headers: &[S]) -> io::Result<()> {
+        self.write_fields(headers, &[])
+    }
+
+    pub fn write_row<S: AsRef<str>>(&mut self, fields: &[S]) -> io::Result<()> {
+        let always_quoted = self.always_quoted.clone();
+        self.write_fields(fields, &always_quoted)
+    }
+
+    fn write_fields<S: AsRef<str>>(
+        &mut self,
+        fields: &[S],
+        always_quoted: &[usize],
+    ) -> io::Result<()> {
+        let mut line = String::new();
+        for (index, field) in fields.iter().enumerate() {
+            if index > 0 {
+                line.push(self.delimiter);
+            }
+            let field = field.as_ref();
+            if always_quoted.contains(&index) || self.needs_quotes(field) {
+                line.push('"');
+                line.push_str(&field.replace('"', "\"\""));
+                line.push('"');
+            } else {
+                line.push_str(field);
+            }
+        }
+        line.push_str("\r\n");
+        self.sink.write_all(line.as_bytes())
+    }
+
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        self.write_row(record.fields())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.sink.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.sink
+    }
+
+    fn needs_quotes(&self, field: &str) -> bool {
+        field
+            .chars()
+            .any(|c| c == self.delimiter || c == '"' || c == '\r' || c == '\n')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_quotes_only_what_needs_quoting() {
+        let mut writer = Writer::new(vec![]);
+        writer
+            .write_row(&[
+                "Red Sox at Mariners",
+                "ROOTNW, FS1",
+                "\"Turn Ahead the Clock\"",
+                "",
+            ])
+            .unwrap();
+        writer.write_row(&["line\nbreak"]).unwrap();
+
+        assert_eq!(
+            "Red Sox at Mariners,\"ROOTNW, FS1\",\"\"\"Turn Ahead the Clock\"\"\",\r\n\"line\nbreak\"\r\n",
+            String::from_utf8(writer.into_inner()).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_round_trips_what_it_reads() {
+        let contents = "SUBJECT,DESCRIPTION,REMINDER ON\r\nAngels at Mariners,\"\",TRUE\r\nRays at Mariners,\"Local TV: ROOTNW ----- Local Radio: 710 ESPN, \"\"delay\"\"\",TRUE\r\n";
+        let mut writer = Writer::with_always_quoted(vec![], &[1]);
+        let records = crate::read_records(contents);
+        writer.write_header(records[0].headers()).unwrap();
+        for record in &records {
+            writer.write_record(record).unwrap();
+        }
+
+        assert_eq!(contents, String::from_utf8(writer.into_inner()).unwrap());
+    }
+}
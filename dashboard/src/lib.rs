@@ -1,7 +1,8 @@
-use game_parser;
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
 pub enum WarningStatus {
     Waiting,
     Completed,
@@ -10,6 +11,96 @@ pub enum WarningStatus {
 
 #[derive(Serialize, Debug, PartialEq)]
 pub struct GameInfo<'a> {
+    pub id: Option<game_parser::GameId>,
     pub game: &'a game_parser::Game,
     pub status: WarningStatus,
+    /// Which schedule each field came from, e.g. "time from the Stats API, overridden by
+    /// overrides.csv".
+    pub provenance: Option<String>,
+}
+
+/// A text about more than one game, such as a homestand heads-up, listed with the games
+/// so that its status survives a reload too.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct AlertInfo<'a> {
+    pub id: game_parser::GameId,
+    pub purpose: &'a str,
+    pub status: WarningStatus,
+}
+
+/// One line of the dashboard.
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum DashboardEntry<'a> {
+    Game(GameInfo<'a>),
+    Alert(AlertInfo<'a>),
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    id: Option<game_parser::GameId>,
+    status: WarningStatus,
+}
+
+/// Reads back the status of each game and heads-up in a dashboard written from
+/// `DashboardEntry`s, so that a game keeps its status when the schedule is reloaded, even
+/// if it was moved.
+pub fn read_statuses(
+    json: &str,
+) -> serde_json::Result<HashMap<game_parser::GameId, WarningStatus>> {
+    let entries: Vec<Entry> = serde_json::from_str(json)?;
+    Ok(entries
+        .into_iter()
+        .filter_map(|entry| Some((entry.id?, entry.status)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reads_back_the_statuses_it_wrote() {
+        let game = |away_team: Option<&str>| game_parser::Game::GameWithDay {
+            start_day: "07/20/19".to_string(),
+            details: game_parser::GameDetails {
+                home_team: Some("Mariners".to_string()),
+                away_team: away_team.map(str::to_string),
+                ..Default::default()
+            },
+        };
+        let (named, unnamed) = (game(Some("Angels")), game(None));
+        let json = serde_json::to_string(&[
+            DashboardEntry::Game(GameInfo {
+                id: game_parser::id::game_id(&named),
+                game: &named,
+                status: WarningStatus::Completed,
+                provenance: None,
+            }),
+            DashboardEntry::Game(GameInfo {
+                id: game_parser::id::game_id(&unnamed),
+                game: &unnamed,
+                status: WarningStatus::Error,
+                provenance: None,
+            }),
+            DashboardEntry::Alert(AlertInfo {
+                id: game_parser::GameId::new("2019-07-19/mariners-homestand"),
+                purpose: "the Mariners start a homestand",
+                status: WarningStatus::Completed,
+            }),
+        ])
+        .unwrap();
+
+        let statuses = read_statuses(&json).unwrap();
+        assert_eq!(2, statuses.len());
+        assert_eq!(
+            Some(&WarningStatus::Completed),
+            statuses.get(&game_parser::GameId::new("2019-07-19/mariners-homestand"))
+        );
+        assert_eq!(
+            Some(&WarningStatus::Completed),
+            statuses.get(&game_parser::GameId::new("2019-07-20/angels-at-mariners"))
+        );
+        assert!(read_statuses("not a dashboard").is_err());
+    }
 }
@@ -0,0 +1,136 @@
+//! Reads who is showing a game out of its description, which the MLB export writes as
+//! "Local TV: ROOTNW- MLBN (out-of-market only) ----- Local Radio: 710 ESPN".
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+pub struct Broadcasts {
+    pub tv: Vec<Broadcaster>,
+    pub radio: Vec<Broadcaster>,
+}
+
+/// A channel or station, with whatever the schedule says in brackets after it, such as
+/// "out-of-market only" or "delay".
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Broadcaster {
+    pub name: String,
+    pub note: Option<String>,
+}
+
+impl Broadcaster {
+    /// Whether the game can be followed on it from Seattle.
+    pub fn is_local(&self) -> bool {
+        match &self.note {
+            Some(note) => !note.to_lowercase().contains("out-of-market"),
+            None => true,
+        }
+    }
+}
+
+impl Broadcasts {
+    pub fn is_empty(&self) -> bool {
+        self.tv.is_empty() && self.radio.is_empty()
+    }
+
+    /// The TV channels and then radio stations the game can be followed on from Seattle.
+    pub fn local(&self) -> Vec<&Broadcaster> {
+        self.tv
+            .iter()
+            .chain(&self.radio)
+            .filter(|broadcaster| broadcaster.is_local())
+            .collect()
+    }
+}
+
+/// The local broadcasters, e.g. "ROOTNW / 710 ESPN".
+impl fmt::Display for Broadcasts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = self
+            .local()
+            .iter()
+            .map(|broadcaster| broadcaster.name.as_str())
+            .collect::<Vec<&str>>();
+        write!(f, "{}", names.join(" / "))
+    }
+}
+
+/// Sections are separated by a run of dashes and start with a label; those whose label
+/// mentions TV or radio list broadcasters separated by "- " or commas. Anything else in
+/// the description is ignored.
+pub fn parse_broadcasts(description: &str) -> Broadcasts {
+    let mut broadcasts = Broadcasts::default();
+    for section in description.split("-----") {
+        let mut parts = section.splitn(2, ':');
+        let (label, list) = match (parts.next(), parts.next()) {
+            (Some(label), Some(list)) => (label.to_lowercase(), list),
+            _ => continue,
+        };
+        let broadcasters = if label.contains("tv") {
+            &mut broadcasts.tv
+        } else if label.contains("radio") {
+            &mut broadcasts.radio
+        } else {
+            continue;
+        };
+        broadcasters.extend(
+            list.split(',')
+                .flat_map(|item| item.split("- "))
+                .filter_map(parse_broadcaster),
+        );
+    }
+    broadcasts
+}
+
+fn parse_broadcaster(item: &str) -> Option<Broadcaster> {
+    let item = item.trim().trim_matches('-').trim();
+    let (name, note) = match item.find('(') {
+        Some(open) => (
+            &item[..open],
+            Some(item[open + 1..].trim_end_matches(')').trim().to_string()),
+        ),
+        None => (item, None),
+    };
+    let name = name.trim();
+    if name.is_empty() {
+        None
+    } else {
+        Some(Broadcaster {
+            name: name.to_string(),
+            note,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn broadcaster(name: &str, note: Option<&str>) -> Broadcaster {
+        Broadcaster {
+            name: name.to_string(),
+            note: note.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn it_reads_tv_and_radio_out_of_the_description() {
+        let broadcasts = parse_broadcasts(
+            "Local TV: ROOTNW- MLBN (out-of-market only) ----- Local Radio: 710 ESPN (delay)",
+        );
+
+        assert_eq!(
+            Broadcasts {
+                tv: vec![
+                    broadcaster("ROOTNW", None),
+                    broadcaster("MLBN", Some("out-of-market only")),
+                ],
+                radio: vec![broadcaster("710 ESPN", Some("delay"))],
+            },
+            broadcasts
+        );
+        assert_eq!("ROOTNW / 710 ESPN", broadcasts.to_string());
+        assert!(parse_broadcasts("ALDS Game 1").is_empty());
+    }
+}
@@ -0,0 +1,51 @@
+//! Header names used by the MLB "download the schedule" calendar export.
+
+use chrono_tz::Tz;
+
+/// `chrono` formats for the export's date and time columns, e.g. `03/28/19` and `04:10 PM`.
+pub const DATE_FORMAT: &str = "%m/%d/%y";
+pub const TIME_FORMAT: &str = "%I:%M %p";
+
+/// The zones the export's wall-clock times are in: every column is Seattle time except
+/// the ones suffixed `ET`. Both follow daylight saving time.
+pub const PACIFIC: Tz = chrono_tz::America::Los_Angeles;
+pub const EASTERN: Tz = chrono_tz::America::New_York;
+
+pub const START_DATE: &str = "START DATE";
+pub const START_TIME: &str = "START TIME";
+pub const START_TIME_ET: &str = "START TIME ET";
+pub const SUBJECT: &str = "SUBJECT";
+pub const LOCATION: &str = "LOCATION";
+pub const DESCRIPTION: &str = "DESCRIPTION";
+pub const END_DATE: &str = "END DATE";
+pub const END_DATE_ET: &str = "END DATE ET";
+pub const END_TIME: &str = "END TIME";
+pub const END_TIME_ET: &str = "END TIME ET";
+pub const REMINDER_OFF: &str = "REMINDER OFF";
+pub const REMINDER_ON: &str = "REMINDER ON";
+pub const REMINDER_DATE: &str = "REMINDER DATE";
+pub const REMINDER_TIME: &str = "REMINDER TIME";
+pub const REMINDER_TIME_ET: &str = "REMINDER TIME ET";
+pub const SHOWTIMEAS_FREE: &str = "SHOWTIMEAS FREE";
+pub const SHOWTIMEAS_BUSY: &str = "SHOWTIMEAS BUSY";
+
+/// Every column of the export, in the order MLB lays them out.
+pub const MLB_HEADERS: [&str; 17] = [
+    START_DATE,
+    START_TIME,
+    START_TIME_ET,
+    SUBJECT,
+    LOCATION,
+    DESCRIPTION,
+    END_DATE,
+    END_DATE_ET,
+    END_TIME,
+    END_TIME_ET,
+    REMINDER_OFF,
+    REMINDER_ON,
+    REMINDER_DATE,
+    REMINDER_TIME,
+    REMINDER_TIME_ET,
+    SHOWTIMEAS_FREE,
+    SHOWTIMEAS_BUSY,
+];
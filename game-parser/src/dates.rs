@@ -0,0 +1,233 @@
+//! Reads the dates schedules are written with. Every source reads its dates here, so a
+//! schedule that writes them another way only needs its `DateFormat` listed.
+
+use std::fmt;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::error::DateError;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DateFormat {
+    /// `03/28/19` or `03/28/2019`, as in the MLB export.
+    MonthDayYear,
+    /// `28/03/19` or `28/03/2019`.
+    DayMonthYear,
+    /// `2019-03-28`, or `20190328` as in iCalendar.
+    Iso8601,
+    /// `March 28, 2019`, `Mar 28 2019` or `28 March 2019`, with or without the weekday
+    /// in front.
+    Textual,
+}
+
+impl fmt::Display for DateFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateFormat::MonthDayYear => write!(f, "MM/DD/YY"),
+            DateFormat::DayMonthYear => write!(f, "DD/MM/YY"),
+            DateFormat::Iso8601 => write!(f, "YYYY-MM-DD"),
+            DateFormat::Textual => write!(f, "a date like March 28, 2019"),
+        }
+    }
+}
+
+impl DateFormat {
+    /// Writes `day` this way, with a two digit year for the numeric formats.
+    pub fn format(self, day: NaiveDate) -> String {
+        let format = match self {
+            DateFormat::MonthDayYear => "%m/%d/%y",
+            DateFormat::DayMonthYear => "%d/%m/%y",
+            DateFormat::Iso8601 => "%Y-%m-%d",
+            DateFormat::Textual => "%B %-d, %Y",
+        };
+        day.format(format).to_string()
+    }
+
+    /// `None` when `value` isn't written this way at all, and `Some(None)` when it is but
+    /// names a day that doesn't exist, like 02/30/19.
+    fn read(self, value: &str) -> Option<Option<NaiveDate>> {
+        match self {
+            DateFormat::MonthDayYear => {
+                let [month, day, year] = numbers(value, '/')?;
+                Some(ymd(year, month, day))
+            }
+            DateFormat::DayMonthYear => {
+                let [day, month, year] = numbers(value, '/')?;
+                Some(ymd(year, month, day))
+            }
+            DateFormat::Iso8601 => {
+                let (year, month, day) = if value.len() == 8 && is_digits(value) {
+                    (&value[..4], &value[4..6], &value[6..])
+                } else {
+                    let mut parts = value.split('-');
+                    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+                        (Some(year), Some(month), Some(day), None) if year.len() == 4 => {
+                            (year, month, day)
+                        }
+                        _ => return None,
+                    }
+                };
+                if ![year, month, day].iter().all(|part| is_digits(part)) {
+                    return None;
+                }
+                Some(ymd(
+                    year.parse().ok()?,
+                    month.parse().ok()?,
+                    day.parse().ok()?,
+                ))
+            }
+            DateFormat::Textual => {
+                let mut parts = value.splitn(2, ',');
+                let value = match (parts.next(), parts.next()) {
+                    (Some(weekday), Some(rest)) if !weekday.chars().any(char::is_numeric) => {
+                        rest.trim()
+                    }
+                    _ => value,
+                };
+                ["%B %d, %Y", "%B %d %Y", "%d %B %Y", "%d %B, %Y"]
+                    .iter()
+                    .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+                    .map(Some)
+            }
+        }
+    }
+}
+
+/// The formats a schedule's dates may be in. When a date reads as different days in two
+/// of them it is an error rather than a guess, which is why `DayMonthYear` isn't one of
+/// the defaults: 03/04/19 would be ambiguous alongside `MonthDayYear`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct DateFormats(Vec<DateFormat>);
+
+impl Default for DateFormats {
+    fn default() -> DateFormats {
+        DateFormats(vec![
+            DateFormat::MonthDayYear,
+            DateFormat::Iso8601,
+            DateFormat::Textual,
+        ])
+    }
+}
+
+impl DateFormats {
+    pub fn new(formats: Vec<DateFormat>) -> DateFormats {
+        DateFormats(formats)
+    }
+
+    pub fn formats(&self) -> &[DateFormat] {
+        &self.0
+    }
+
+    /// The first of the formats that reads `value` as a day, so that a date written back
+    /// into a schedule can be written the way the schedule writes them.
+    pub fn format_of(&self, value: &str) -> Option<DateFormat> {
+        self.0
+            .iter()
+            .copied()
+            .find(|format| matches!(format.read(value.trim()), Some(Some(_))))
+    }
+
+    pub fn parse(&self, value: &str) -> Result<NaiveDate, DateError> {
+        let value = value.trim();
+        let readings = self
+            .0
+            .iter()
+            .filter_map(|format| format.read(value))
+            .collect::<Vec<Option<NaiveDate>>>();
+
+        let mut days = readings
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<NaiveDate>>();
+        days.sort();
+        days.dedup();
+        match days.as_slice() {
+            [day] => Ok(*day),
+            [] if readings.is_empty() => Err(DateError::Unrecognised {
+                expected: self.0.clone(),
+            }),
+            [] => Err(DateError::NoSuchDay),
+            _ => Err(DateError::Ambiguous { readings: days }),
+        }
+    }
+}
+
+/// The three numbers of a date like `03/28/19`, with a two digit year taken to be this
+/// century's.
+fn numbers(value: &str, separator: char) -> Option<[u32; 3]> {
+    let parts = value.split(separator).collect::<Vec<&str>>();
+    match parts.as_slice() {
+        [first, second, year]
+            if [first, second, year].iter().all(|part| is_digits(part))
+                && first.len() <= 2
+                && second.len() <= 2
+                && (year.len() == 2 || year.len() == 4) =>
+        {
+            let year = year.parse::<u32>().ok()?;
+            Some([
+                first.parse().ok()?,
+                second.parse().ok()?,
+                if year < 100 { 2000 + year } else { year },
+            ])
+        }
+        _ => None,
+    }
+}
+
+fn ymd(year: u32, month: u32, day: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year as i32, month, day)
+}
+
+fn is_digits(part: &str) -> bool {
+    !part.is_empty() && part.chars().all(|c| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reads_every_format_and_refuses_to_guess() {
+        let march_28 = NaiveDate::from_ymd(2019, 3, 28);
+        let formats = DateFormats::default();
+        for value in &[
+            "03/28/19",
+            "3/28/2019",
+            "2019-03-28",
+            "20190328",
+            "March 28, 2019",
+            "Mar 28 2019",
+            "28 March 2019",
+            "Thursday, March 28, 2019",
+        ] {
+            assert_eq!(Ok(march_28), formats.parse(value), "{}", value);
+        }
+
+        assert_eq!(Err(DateError::NoSuchDay), formats.parse("02/30/19"));
+        assert_eq!(
+            "expected MM/DD/YY, YYYY-MM-DD or a date like March 28, 2019",
+            formats.parse("28.03.2019").unwrap_err().to_string()
+        );
+
+        let both = DateFormats::new(vec![DateFormat::MonthDayYear, DateFormat::DayMonthYear]);
+        assert_eq!(Ok(march_28), both.parse("28/03/2019"));
+        assert_eq!(
+            Err(DateError::Ambiguous {
+                readings: vec![
+                    NaiveDate::from_ymd(2019, 3, 4),
+                    NaiveDate::from_ymd(2019, 4, 3)
+                ],
+            }),
+            both.parse("03/04/19")
+        );
+        assert_eq!(Ok(march_28), both.parse("03/28/19"));
+
+        for format in formats.formats() {
+            assert_eq!(Ok(march_28), formats.parse(&format.format(march_28)));
+        }
+        assert_eq!(Some(DateFormat::DayMonthYear), both.format_of("28/03/2019"));
+    }
+}
@@ -0,0 +1,208 @@
+//! What changed between two versions of a schedule, such as the CSV downloaded at the start
+//! of the season and one downloaded mid-season.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::columns::PACIFIC;
+use crate::id::game_id;
+use crate::status::game_day;
+use crate::{Game, GameId};
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum Change {
+    Added {
+        id: GameId,
+        game: Game,
+    },
+    Removed {
+        id: GameId,
+        game: Game,
+    },
+    TimeChanged {
+        id: GameId,
+        game: Game,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    },
+    VenueChanged {
+        id: GameId,
+        game: Game,
+        from: Option<String>,
+        to: Option<String>,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Added { id, game } => {
+                write!(f, "added {}, {}", matchup(id, game), when(game))
+            }
+            Change::Removed { id, game } => {
+                write!(f, "removed {}, {}", matchup(id, game), when(game))
+            }
+            Change::TimeChanged { id, game, from, .. } => {
+                let before = match from {
+                    Some(from) => format_start(*from),
+                    None => "a time to be decided".to_string(),
+                };
+                write!(
+                    f,
+                    "{} moved from {} to {}",
+                    matchup(id, game),
+                    before,
+                    when(game)
+                )
+            }
+            Change::VenueChanged { id, game, from, to } => write!(
+                f,
+                "{} moved from {} to {}",
+                matchup(id, game),
+                from.as_deref().unwrap_or("an unknown venue"),
+                to.as_deref().unwrap_or("an unknown venue")
+            ),
+        }
+    }
+}
+
+/// Matches the games of `old` and `new` by `id::game_id`, listing games only in `new` as
+/// added, games only in `old` as removed, and games in both whose start or venue differs.
+/// A game whose time was TBD and is now set counts as a time change. Games without an ID
+/// can't be matched and are left out. Changes come in ID order.
+pub fn diff_schedules(old: &[Game], new: &[Game]) -> Vec<Change> {
+    let by_id = |games: &[Game]| {
+        games
+            .iter()
+            .filter_map(|game| Some((game_id(game)?, game.clone())))
+            .collect::<BTreeMap<GameId, Game>>()
+    };
+    let mut old = by_id(old);
+    let new = by_id(new);
+
+    let mut changes = vec![];
+    for (id, game) in new {
+        let before = match old.remove(&id) {
+            Some(before) => before,
+            None => {
+                changes.push(Change::Added { id, game });
+                continue;
+            }
+        };
+
+        let (from, to) = (start(&before), start(&game));
+        if from != to {
+            changes.push(Change::TimeChanged {
+                id: id.clone(),
+                game: game.clone(),
+                from,
+                to,
+            });
+        }
+        let (from, to) = (&before.details().venue, &game.details().venue);
+        if from != to {
+            changes.push(Change::VenueChanged {
+                from: from.clone(),
+                to: to.clone(),
+                id,
+                game,
+            });
+        }
+    }
+    changes.extend(
+        old.into_iter()
+            .map(|(id, game)| Change::Removed { id, game }),
+    );
+    changes.sort_by(|a, b| change_id(a).cmp(change_id(b)));
+    changes
+}
+
+fn change_id(change: &Change) -> &GameId {
+    match change {
+        Change::Added { id, .. }
+        | Change::Removed { id, .. }
+        | Change::TimeChanged { id, .. }
+        | Change::VenueChanged { id, .. } => id,
+    }
+}
+
+fn start(game: &Game) -> Option<DateTime<Utc>> {
+    match game {
+        Game::PerfectlyScheduledGame {
+            start_date_time, ..
+        } => Some(*start_date_time),
+        _ => None,
+    }
+}
+
+fn matchup(id: &GameId, game: &Game) -> String {
+    let details = game.details();
+    match (&details.away_team, &details.home_team) {
+        (Some(away), Some(home)) => format!("{} at {}", away, home),
+        _ => id.to_string(),
+    }
+}
+
+fn format_start(start: DateTime<Utc>) -> String {
+    start
+        .with_timezone(&PACIFIC)
+        .format("%a %b %-d at %-I:%M %p")
+        .to_string()
+}
+
+fn when(game: &Game) -> String {
+    match (start(game), game_day(game)) {
+        (Some(start), _) => format_start(start),
+        (None, Some(day)) => format!("{} with the time TBD", day.format("%a %b %-d")),
+        (None, None) => "an unknown time".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games_from_csv as games;
+
+    #[test]
+    fn it_lists_what_changed_between_two_schedules() {
+        let old = games(
+            "START DATE,START TIME,SUBJECT,LOCATION\r\n\
+             07/20/19,,Angels at Mariners - Time TBD,T-Mobile Park - Seattle\r\n\
+             07/21/19,01:10 PM,Red Sox at Mariners,T-Mobile Park - Seattle\r\n\
+             07/22/19,01:10 PM,Rays at Mariners,T-Mobile Park - Seattle\r\n\
+             07/23/19,07:10 PM,Twins at Mariners,T-Mobile Park - Seattle\r\n",
+        );
+        let new = games(
+            "START DATE,START TIME,SUBJECT,LOCATION\r\n\
+             07/20/19,07:10 PM,Angels at Mariners,T-Mobile Park - Seattle\r\n\
+             07/21/19,01:10 PM,Red Sox at Mariners,Tokyo Dome - Tokyo\r\n\
+             07/23/19,07:10 PM,Twins at Mariners,T-Mobile Park - Seattle\r\n\
+             07/24/19,12:40 PM,Twins at Mariners,T-Mobile Park - Seattle\r\n",
+        );
+
+        let changes = diff_schedules(&old, &new);
+        assert_eq!(
+            vec![
+                "Angels at Mariners moved from a time to be decided to Sat Jul 20 at 7:10 PM",
+                "Red Sox at Mariners moved from T-Mobile Park - Seattle to Tokyo Dome - Tokyo",
+                "removed Rays at Mariners, Mon Jul 22 at 1:10 PM",
+                "added Twins at Mariners, Wed Jul 24 at 12:40 PM",
+            ],
+            changes
+                .iter()
+                .map(Change::to_string)
+                .collect::<Vec<String>>()
+        );
+
+        let json = serde_json::to_value(&changes[0]).unwrap();
+        assert_eq!("time_changed", json["change"]);
+        assert_eq!("2019-07-20/angels-at-mariners", json["id"]);
+        assert_eq!(serde_json::Value::Null, json["from"]);
+        assert_eq!("2019-07-21T02:10:00Z", json["to"]);
+        assert!(diff_schedules(&new, &new).is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use crate::dates::DateFormat;
+
+/// Why a schedule row could not be turned into a `Game`. Each variant names the record's
+/// line, the column that failed and what was in it.
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    BadDate {
+        line: usize,
+        field: String,
+        value: String,
+        reason: String,
+    },
+    /// The date reads as more than one day in the `DateFormats` it may be in.
+    AmbiguousDate {
+        line: usize,
+        field: String,
+        value: String,
+        readings: Vec<NaiveDate>,
+    },
+    BadTime {
+        line: usize,
+        field: String,
+        value: String,
+        reason: String,
+    },
+    BadMeridiem {
+        line: usize,
+        field: String,
+        value: String,
+    },
+    HourOutOfRange {
+        line: usize,
+        field: String,
+        value: String,
+        hour: u32,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::BadDate {
+                line,
+                field,
+                value,
+                reason,
+            } => write!(
+                f,
+                "line {}: {} {:?} is not a date: {}",
+                line, field, value, reason
+            ),
+            ParseError::AmbiguousDate {
+                line,
+                field,
+                value,
+                readings,
+            } => write!(
+                f,
+                "line {}: {} {:?} is ambiguous: {}",
+                line,
+                field,
+                value,
+                DateError::Ambiguous {
+                    readings: readings.clone()
+                }
+            ),
+            ParseError::BadTime {
+                line,
+                field,
+                value,
+                reason,
+            } => write!(
+                f,
+                "line {}: {} {:?} is not a time: {}",
+                line, field, value, reason
+            ),
+            ParseError::BadMeridiem { line, field, value } => write!(
+                f,
+                "line {}: {} {:?} does not end in AM or PM",
+                line, field, value
+            ),
+            ParseError::HourOutOfRange {
+                line,
+                field,
+                value,
+                hour,
+            } => write!(
+                f,
+                "line {}: {} {:?} has hour {}, which is not between 1 and 12",
+                line, field, value, hour
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Why `DateFormats::parse` could not read a date.
+#[derive(Debug, PartialEq)]
+pub enum DateError {
+    Unrecognised { expected: Vec<DateFormat> },
+    NoSuchDay,
+    Ambiguous { readings: Vec<NaiveDate> },
+}
+
+impl fmt::Display for DateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateError::Unrecognised { expected } => {
+                write!(f, "expected {}", one_of(expected))
+            }
+            DateError::NoSuchDay => write!(f, "there is no such day"),
+            DateError::Ambiguous { readings } => {
+                let days = readings
+                    .iter()
+                    .map(|day| day.format("%a %b %-d %Y"))
+                    .collect::<Vec<_>>();
+                write!(f, "it could be {}", one_of(&days))
+            }
+        }
+    }
+}
+
+impl std::error::Error for DateError {}
+
+/// "a, b or c".
+fn one_of<T: fmt::Display>(items: &[T]) -> String {
+    let mut items = items.iter().map(T::to_string).collect::<Vec<String>>();
+    match items.pop() {
+        Some(last) if !items.is_empty() => format!("{} or {}", items.join(", "), last),
+        Some(last) => last,
+        None => "nothing".to_string(),
+    }
+}
+
+/// Why a Stats API schedule could not be read.
+#[derive(Debug, PartialEq)]
+pub enum StatsApiError {
+    Io { path: String, error: String },
+    Http { url: String, error: String },
+    Json { error: String },
+}
+
+impl fmt::Display for StatsApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatsApiError::Io { path, error } => write!(f, "could not read {}: {}", path, error),
+            StatsApiError::Http { url, error } => {
+                write!(f, "could not fetch {}: {}", url, error)
+            }
+            StatsApiError::Json { error } => {
+                write!(f, "not a Stats API schedule: {}", error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for StatsApiError {}
+
+/// Why an `EventSource` could not be read at all.
+#[derive(Debug, PartialEq)]
+pub enum SourceError {
+    Io { path: String, error: String },
+    StatsApi { error: StatsApiError },
+    Config { reason: String },
+}
+
+impl fmt::Display for SourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SourceError::Io { path, error } => write!(f, "could not read {}: {}", path, error),
+            SourceError::StatsApi { error } => write!(f, "{}", error),
+            SourceError::Config { reason } => write!(f, "bad source: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for SourceError {}
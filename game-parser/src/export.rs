@@ -0,0 +1,421 @@
+//! Turns games back into rows of the MLB calendar export, so a schedule can be read,
+//! corrected by hand and written back out.
+
+use std::io::Write;
+
+use chrono::{DateTime, Utc};
+use csv_reader::{Record, Writer};
+
+use crate::columns::*;
+use crate::dates::{DateFormat, DateFormats};
+use crate::status::game_day;
+use crate::{parse_date_time, parse_details_with, parse_game_with, Game, GameDetails};
+
+/// Builds the export row for `game`, laid out like `source` when it was read from a file
+/// and like `MLB_HEADERS` otherwise. Fields are copied from `source` untouched, so a game
+/// that has not changed comes back out byte for byte. When it has moved, the start, end and
+/// reminder columns are all shifted by the same amount, unless the game's end time was
+/// changed too. A game without a start time, such as one whose time is TBD, keeps its day.
+/// `source` is read with `date_formats`, and dates written into it keep its START DATE's
+/// format.
+pub fn to_schedule_row(
+    game: &Game,
+    source: Option<&Record>,
+    date_formats: &DateFormats,
+) -> Vec<String> {
+    let (headers, mut row): (Vec<String>, Vec<String>) = match source {
+        Some(record) => (record.headers().to_vec(), record.fields().to_vec()),
+        None => (
+            MLB_HEADERS.iter().map(|h| h.to_string()).collect(),
+            vec![String::new(); MLB_HEADERS.len()],
+        ),
+    };
+    row.resize(headers.len(), String::new());
+
+    let details = game.details();
+    let previous = source
+        .map(|record| parse_details_with(record, date_formats))
+        .unwrap_or_default();
+    let date_format = source
+        .and_then(|record| record.get(START_DATE))
+        .and_then(|value| date_formats.format_of(value))
+        .unwrap_or(DateFormat::MonthDayYear);
+    set_details(&mut row, &headers, details, &previous);
+
+    let start_date_time = match game {
+        Game::PerfectlyScheduledGame {
+            start_date_time, ..
+        } => *start_date_time,
+        _ => {
+            set_day(&mut row, &headers, game, source, date_formats, date_format);
+            return row;
+        }
+    };
+    let end_changed = details.end_date_time != previous.end_date_time;
+
+    match source {
+        Some(record) => {
+            let previous_start = match parse_game_with(record, date_formats) {
+                Ok(Game::PerfectlyScheduledGame {
+                    start_date_time, ..
+                }) => Some(start_date_time),
+                _ => None,
+            };
+            if previous_start == Some(start_date_time) && !end_changed {
+                return row;
+            }
+
+            let moved_by = previous_start.map(|previous| start_date_time - previous);
+            let end = if end_changed {
+                details.end_date_time
+            } else {
+                parse_date_time(record, END_DATE, END_TIME, date_formats)
+                    .ok()
+                    .and_then(|end| moved_by.map(|moved_by| end + moved_by))
+            };
+            let reminder = parse_date_time(record, REMINDER_DATE, REMINDER_TIME, date_formats).ok();
+
+            set_times(
+                &mut row,
+                &headers,
+                date_format,
+                &START_COLUMNS,
+                start_date_time,
+            );
+            if let Some(end) = end {
+                set_times(&mut row, &headers, date_format, &END_COLUMNS, end);
+            }
+            if let (Some(reminder), Some(moved_by)) = (reminder, moved_by) {
+                set_times(
+                    &mut row,
+                    &headers,
+                    date_format,
+                    &REMINDER_COLUMNS,
+                    reminder + moved_by,
+                );
+            }
+        }
+        None => {
+            set_times(
+                &mut row,
+                &headers,
+                date_format,
+                &START_COLUMNS,
+                start_date_time,
+            );
+            if let Some(end) = details.end_date_time {
+                set_times(&mut row, &headers, date_format, &END_COLUMNS, end);
+            }
+            set_times(
+                &mut row,
+                &headers,
+                date_format,
+                &REMINDER_COLUMNS,
+                start_date_time - chrono::Duration::hours(1),
+            );
+            set_reminder_flags(&mut row, &headers);
+        }
+    }
+
+    row
+}
+
+/// A CSV writer that quotes the DESCRIPTION column the way the MLB export does.
+pub fn schedule_writer<W: Write, S: AsRef<str>>(sink: W, headers: &[S]) -> Writer<W> {
+    let always_quoted: Vec<usize> = headers
+        .iter()
+        .position(|header| header.as_ref() == DESCRIPTION)
+        .into_iter()
+        .collect();
+    Writer::with_always_quoted(sink, &always_quoted)
+}
+
+/// Writes the day of a game without a start time into START DATE, unless the row's own
+/// START DATE is already that day.
+fn set_day(
+    row: &mut [String],
+    headers: &[String],
+    game: &Game,
+    source: Option<&Record>,
+    date_formats: &DateFormats,
+    date_format: DateFormat,
+) {
+    let day = match game_day(game) {
+        Some(day) => day,
+        None => return,
+    };
+    let previous_day = source
+        .and_then(|record| parse_game_with(record, date_formats).ok())
+        .as_ref()
+        .and_then(game_day);
+    if previous_day == Some(day) {
+        return;
+    }
+
+    set(row, headers, START_DATE, date_format.format(day));
+    if source.is_none() {
+        set_reminder_flags(row, headers);
+    }
+}
+
+fn set_reminder_flags(row: &mut [String], headers: &[String]) {
+    set(row, headers, REMINDER_OFF, "FALSE".to_string());
+    set(row, headers, REMINDER_ON, "TRUE".to_string());
+    set(row, headers, SHOWTIMEAS_FREE, "FREE".to_string());
+    set(row, headers, SHOWTIMEAS_BUSY, "BUSY".to_string());
+}
+
+/// Only rewrites the SUBJECT, LOCATION and DESCRIPTION columns that differ from what
+/// `previous` read out of them, so the export's own spelling survives.
+fn set_details(
+    row: &mut [String],
+    headers: &[String],
+    details: &GameDetails,
+    previous: &GameDetails,
+) {
+    if (&details.away_team, &details.home_team) != (&previous.away_team, &previous.home_team) {
+        if let (Some(away), Some(home)) = (&details.away_team, &details.home_team) {
+            set(row, headers, SUBJECT, format!("{} at {}", away, home));
+        }
+    }
+    if details.venue != previous.venue {
+        set(
+            row,
+            headers,
+            LOCATION,
+            details.venue.clone().unwrap_or_default(),
+        );
+    }
+    if details.description != previous.description {
+        set(
+            row,
+            headers,
+            DESCRIPTION,
+            details.description.clone().unwrap_or_default(),
+        );
+    }
+}
+
+/// The columns a moment is written to, in Seattle and in eastern time.
+struct TimeColumns {
+    date: &'static str,
+    time: &'static str,
+    eastern_date: Option<&'static str>,
+    eastern_time: &'static str,
+}
+
+const START_COLUMNS: TimeColumns = TimeColumns {
+    date: START_DATE,
+    time: START_TIME,
+    eastern_date: None,
+    eastern_time: START_TIME_ET,
+};
+
+const END_COLUMNS: TimeColumns = TimeColumns {
+    date: END_DATE,
+    time: END_TIME,
+    eastern_date: Some(END_DATE_ET),
+    eastern_time: END_TIME_ET,
+};
+
+const REMINDER_COLUMNS: TimeColumns = TimeColumns {
+    date: REMINDER_DATE,
+    time: REMINDER_TIME,
+    eastern_date: None,
+    eastern_time: REMINDER_TIME_ET,
+};
+
+fn set_times(
+    row: &mut [String],
+    headers: &[String],
+    date_format: DateFormat,
+    columns: &TimeColumns,
+    date_time: DateTime<Utc>,
+) {
+    let local = date_time.with_timezone(&PACIFIC);
+    let eastern = date_time.with_timezone(&EASTERN);
+    set(
+        row,
+        headers,
+        columns.date,
+        date_format.format(local.naive_local().date()),
+    );
+    set(
+        row,
+        headers,
+        columns.time,
+        local.format(TIME_FORMAT).to_string(),
+    );
+    if let Some(eastern_date) = columns.eastern_date {
+        set(
+            row,
+            headers,
+            eastern_date,
+            date_format.format(eastern.naive_local().date()),
+        );
+    }
+    set(
+        row,
+        headers,
+        columns.eastern_time,
+        eastern.format(TIME_FORMAT).to_string(),
+    );
+}
+
+fn set(row: &mut [String], headers: &[String], column: &str, value: String) {
+    if let Some(index) = headers.iter().position(|header| header == column) {
+        row[index] = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_details, parse_game_from, GameStatus, GameType};
+    use chrono::TimeZone;
+
+    const SCHEDULE: &str = "START DATE,START TIME,START TIME ET,SUBJECT,LOCATION,DESCRIPTION,END DATE,END DATE ET,END TIME,END TIME ET,REMINDER OFF,REMINDER ON,REMINDER DATE,REMINDER TIME,REMINDER TIME ET,SHOWTIMEAS FREE,SHOWTIMEAS BUSY\r\n\
+03/25/19,07:10 PM,10:10 PM,Padres at Mariners,T-Mobile Park - Seattle,\"Local TV: ROOTNW ----- Local Radio: 710 ESPN\",03/25/19,03/26/19,10:10 PM,01:10 AM,FALSE,TRUE,03/25/19,06:10 PM,09:10 PM,FREE,BUSY\r\n\
+07/09/19,,,NL All-Stars at AL All-Stars - Time TBD,Progressive Field - Cleveland,\"\",07/09/19,07/09/19,,,FALSE,TRUE,07/09/19,,,FREE,BUSY\r\n";
+
+    fn write(headers: &[String], rows: &[Vec<String>]) -> String {
+        let mut writer = schedule_writer(vec![], headers);
+        writer.write_header(headers).unwrap();
+        for row in rows {
+            writer.write_row(row).unwrap();
+        }
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn it_writes_unchanged_games_back_byte_for_byte() {
+        let records = csv_reader::read_records(SCHEDULE);
+        let rows: Vec<Vec<String>> = records
+            .iter()
+            .map(|record| {
+                let game = parse_game_from(record).unwrap();
+                to_schedule_row(&game, Some(record), &DateFormats::default())
+            })
+            .collect();
+
+        assert_eq!(SCHEDULE, write(records[0].headers(), &rows));
+    }
+
+    #[test]
+    fn it_shifts_every_time_column_when_a_game_moves() {
+        let records = csv_reader::read_records(SCHEDULE);
+        let moved = Game::PerfectlyScheduledGame {
+            start_date_time: Utc.ymd(2019, 3, 26).and_hms(3, 40, 0),
+            details: parse_details(&records[0]),
+        };
+
+        assert_eq!(
+            vec![
+                "03/25/19",
+                "08:40 PM",
+                "11:40 PM",
+                "Padres at Mariners",
+                "T-Mobile Park - Seattle",
+                "Local TV: ROOTNW ----- Local Radio: 710 ESPN",
+                "03/25/19",
+                "03/26/19",
+                "11:40 PM",
+                "02:40 AM",
+                "FALSE",
+                "TRUE",
+                "03/25/19",
+                "07:40 PM",
+                "10:40 PM",
+                "FREE",
+                "BUSY"
+            ],
+            to_schedule_row(&moved, Some(&records[0]), &DateFormats::default())
+        );
+    }
+
+    #[test]
+    fn it_builds_rows_for_games_without_a_source() {
+        let game = Game::PerfectlyScheduledGame {
+            start_date_time: Utc.ymd(2019, 3, 29).and_hms(2, 10, 0),
+            details: GameDetails {
+                home_team: Some("Mariners".to_string()),
+                away_team: Some("Red Sox".to_string()),
+                venue: Some("T-Mobile Park - Seattle".to_string()),
+                end_date_time: Some(Utc.ymd(2019, 3, 29).and_hms(5, 10, 0)),
+                description: None,
+                game_type: GameType::RegularSeason,
+                status: GameStatus::Scheduled,
+                doubleheader: None,
+                source_id: None,
+                broadcasts: Default::default(),
+            },
+        };
+
+        assert_eq!(
+            vec![
+                "03/28/19",
+                "07:10 PM",
+                "10:10 PM",
+                "Red Sox at Mariners",
+                "T-Mobile Park - Seattle",
+                "",
+                "03/28/19",
+                "03/29/19",
+                "10:10 PM",
+                "01:10 AM",
+                "FALSE",
+                "TRUE",
+                "03/28/19",
+                "06:10 PM",
+                "09:10 PM",
+                "FREE",
+                "BUSY"
+            ],
+            to_schedule_row(&game, None, &DateFormats::default())
+        );
+    }
+
+    #[test]
+    fn it_keeps_the_schedules_own_date_format() {
+        let records = csv_reader::read_records(
+            "START DATE,START TIME,SUBJECT\r\n2019-07-09,07:10 PM,Twins at Mariners\r\n",
+        );
+        let formats = DateFormats::default();
+        let game = parse_game_with(&records[0], &formats).unwrap();
+        let moved = Game::PerfectlyScheduledGame {
+            start_date_time: Utc.ymd(2019, 7, 11).and_hms(2, 10, 0),
+            details: game.details().clone(),
+        };
+
+        assert_eq!(
+            records[0].fields().to_vec(),
+            to_schedule_row(&game, Some(&records[0]), &formats)
+        );
+        assert_eq!(
+            vec!["2019-07-10", "07:10 PM", "Twins at Mariners"],
+            to_schedule_row(&moved, Some(&records[0]), &formats)
+        );
+    }
+
+    #[test]
+    fn it_round_trips_games_without_a_start_time() {
+        let game = Game::GameWithDay {
+            start_day: "07/09/19".to_string(),
+            details: GameDetails {
+                home_team: Some("Mariners".to_string()),
+                away_team: Some("Twins".to_string()),
+                venue: Some("T-Mobile Park - Seattle".to_string()),
+                status: GameStatus::TimeTBD,
+                ..Default::default()
+            },
+        };
+        let headers = MLB_HEADERS
+            .iter()
+            .map(|header| header.to_string())
+            .collect::<Vec<String>>();
+        let row = to_schedule_row(&game, None, &DateFormats::default());
+
+        assert_eq!("07/09/19", row[0]);
+        assert_eq!(vec![game], crate::games_from_csv(&write(&headers, &[row])));
+    }
+}
@@ -0,0 +1,182 @@
+//! Decides which games are worth alerting about.
+
+use std::fmt;
+
+use crate::registry::Registry;
+use crate::{Game, GameType};
+
+/// Why a game was left out.
+#[derive(Debug, PartialEq)]
+pub enum SkipReason {
+    NoVenue,
+    FarAway { venue: String },
+    GameType { game_type: GameType },
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::NoVenue => write!(f, "the schedule does not say where it is played"),
+            SkipReason::FarAway { venue } => write!(f, "{} is not a venue nearby", venue),
+            SkipReason::GameType { game_type } => {
+                write!(f, "{} games are not alerted about", game_type)
+            }
+        }
+    }
+}
+
+/// Keeps games played at one of a set of venues. Venues are compared ignoring case,
+/// either in full ("T-Mobile Park - Seattle") or by the stadium name before the city
+/// ("T-Mobile Park").
+#[derive(Debug, Clone)]
+pub struct VenueFilter {
+    venues: Vec<String>,
+}
+
+impl VenueFilter {
+    pub fn new<S: AsRef<str>>(venues: &[S]) -> VenueFilter {
+        VenueFilter {
+            venues: venues
+                .iter()
+                .map(|venue| venue.as_ref().trim().to_lowercase())
+                .collect(),
+        }
+    }
+
+    /// Like `new`, but a venue the registry knows also matches by its registry name and
+    /// aliases, so that "T-Mobile Park - Seattle" still matches once `Registry::normalise`
+    /// has renamed the game's venue to "T-Mobile Park".
+    pub fn with_registry<S: AsRef<str>>(venues: &[S], registry: &Registry) -> VenueFilter {
+        let mut names = venues
+            .iter()
+            .map(|venue| venue.as_ref().to_string())
+            .collect::<Vec<String>>();
+        for venue in venues
+            .iter()
+            .filter_map(|venue| registry.venue(venue.as_ref()))
+        {
+            names.push(venue.name.clone());
+            names.extend(venue.aliases.iter().cloned());
+        }
+        VenueFilter::new(&names)
+    }
+
+    pub fn check(&self, game: &Game) -> Result<(), SkipReason> {
+        let venue = game.details().venue.as_ref().ok_or(SkipReason::NoVenue)?;
+        let full_name = venue.trim().to_lowercase();
+        let stadium = full_name.split(" - ").next().unwrap_or_default();
+
+        if self
+            .venues
+            .iter()
+            .any(|near| *near == full_name || *near == stadium)
+        {
+            Ok(())
+        } else {
+            Err(SkipReason::FarAway {
+                venue: venue.to_string(),
+            })
+        }
+    }
+}
+
+/// Keeps games of the given types, e.g. everything but spring training.
+#[derive(Debug, Clone)]
+pub struct GameTypeFilter {
+    game_types: Vec<GameType>,
+}
+
+impl GameTypeFilter {
+    pub fn new(game_types: &[GameType]) -> GameTypeFilter {
+        GameTypeFilter {
+            game_types: game_types.to_vec(),
+        }
+    }
+
+    pub fn check(&self, game: &Game) -> Result<(), SkipReason> {
+        let game_type = game.details().game_type;
+        if self.game_types.contains(&game_type) {
+            Ok(())
+        } else {
+            Err(SkipReason::GameType { game_type })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_game_from;
+
+    #[test]
+    fn it_only_keeps_games_at_nearby_venues() {
+        let records =
+            csv_reader::read_records(include_str!("../../seattle-mariners-home-schedule.csv"));
+        let filter = VenueFilter::new(&["t-mobile park"]);
+
+        let mut skipped = records
+            .iter()
+            .map(|record| parse_game_from(record).unwrap())
+            .filter_map(|game| filter.check(&game).err())
+            .map(|reason| reason.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(15, skipped.len());
+
+        skipped.dedup();
+        assert_eq!(
+            vec![
+                "Peoria Stadium - Peoria is not a venue nearby",
+                "Progressive Field - Cleveland is not a venue nearby",
+            ],
+            skipped
+        );
+        assert_eq!(
+            Err(SkipReason::NoVenue),
+            filter.check(&Game::GameWithDay {
+                start_day: "07/09/19".to_string(),
+                details: Default::default(),
+            })
+        );
+    }
+
+    #[test]
+    fn it_matches_venues_by_their_registry_names() {
+        let registry = Registry::sodo();
+        let filter = VenueFilter::with_registry(&["T-Mobile Park - Seattle"], &registry);
+        let mut game = Game::GameWithDay {
+            start_day: "07/09/19".to_string(),
+            details: crate::GameDetails {
+                venue: Some("T-Mobile Park - Seattle".to_string()),
+                ..Default::default()
+            },
+        };
+
+        assert_eq!(Ok(()), filter.check(&game));
+        registry.normalise(&mut game);
+        assert_eq!(Ok(()), filter.check(&game));
+        assert!(VenueFilter::new(&["T-Mobile Park - Seattle"])
+            .check(&game)
+            .is_err());
+    }
+
+    #[test]
+    fn it_only_keeps_the_chosen_game_types() {
+        let filter = GameTypeFilter::new(&[GameType::RegularSeason, GameType::Postseason]);
+        let game = |game_type| Game::GameWithDay {
+            start_day: "03/01/19".to_string(),
+            details: crate::GameDetails {
+                game_type,
+                ..Default::default()
+            },
+        };
+
+        assert_eq!(Ok(()), filter.check(&game(GameType::Postseason)));
+        assert_eq!(
+            "spring training games are not alerted about",
+            filter
+                .check(&game(GameType::SpringTraining))
+                .unwrap_err()
+                .to_string()
+        );
+    }
+}
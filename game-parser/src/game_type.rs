@@ -0,0 +1,192 @@
+//! Tells spring training and postseason games apart from the regular season. The MLB
+//! export has no column for it, so it is worked out from where and when a game is played.
+
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::GameDetails;
+
+/// Cactus and Grapefruit League cities, as they appear after the " - " in LOCATION. Phoenix
+/// and Tampa are left out: the D-backs and Rays open their regular season there in March.
+const SPRING_TRAINING_CITIES: [&str; 18] = [
+    "Peoria",
+    "Surprise",
+    "Scottsdale",
+    "Mesa",
+    "Tempe",
+    "Glendale",
+    "Goodyear",
+    "Fort Myers",
+    "Clearwater",
+    "Dunedin",
+    "Jupiter",
+    "Sarasota",
+    "Bradenton",
+    "Lakeland",
+    "Port St. Lucie",
+    "West Palm Beach",
+    "Kissimmee",
+    "North Port",
+];
+
+const POSTSEASON_NAMES: [&str; 6] = [
+    "Wild Card",
+    "Division Series",
+    "ALDS",
+    "ALCS",
+    "Championship Series",
+    "World Series",
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameType {
+    SpringTraining,
+    #[default]
+    RegularSeason,
+    Postseason,
+}
+
+impl fmt::Display for GameType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameType::SpringTraining => write!(f, "spring training"),
+            GameType::RegularSeason => write!(f, "regular season"),
+            GameType::Postseason => write!(f, "postseason"),
+        }
+    }
+}
+
+/// Games whose subject or description names a playoff round are postseason, and ones that
+/// say "Spring Training" are spring training. Failing that, games in a spring training
+/// city from March on back are spring training, and so is any game in February. The regular season can run into October, so
+/// only November games are taken to be postseason by their date alone. Everything else is
+/// regular season.
+pub fn classify(
+    start_day: Option<NaiveDate>,
+    subject: Option<&str>,
+    details: &GameDetails,
+) -> GameType {
+    let city = details
+        .venue
+        .as_ref()
+        .and_then(|venue| venue.rsplit(" - ").next())
+        .map(str::trim);
+    let month = start_day.map(|day| day.month());
+    let mentions = |names: &[&str]| {
+        subject
+            .into_iter()
+            .chain(details.description.as_deref())
+            .any(|text| names.iter().any(|name| text.contains(name)))
+    };
+    let in_spring_training_city = city.is_some_and(|city| SPRING_TRAINING_CITIES.contains(&city))
+        && month.is_some_and(|month| month <= 3);
+
+    if mentions(&POSTSEASON_NAMES) {
+        GameType::Postseason
+    } else if mentions(&["Spring Training"]) || in_spring_training_city || month == Some(2) {
+        GameType::SpringTraining
+    } else if month == Some(11) {
+        GameType::Postseason
+    } else {
+        GameType::RegularSeason
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_game_from;
+
+    #[test]
+    fn it_classifies_the_shipped_schedule() {
+        let records =
+            csv_reader::read_records(include_str!("../../seattle-mariners-home-schedule.csv"));
+        let count = |game_type: GameType| {
+            records
+                .iter()
+                .map(|record| parse_game_from(record).unwrap())
+                .filter(|game| game.details().game_type == game_type)
+                .count()
+        };
+
+        // The hand-edited first row has the Peoria game starting in May, which can't be
+        // spring training any more.
+        assert_eq!(13, count(GameType::SpringTraining));
+        assert_eq!(85, count(GameType::RegularSeason));
+        assert_eq!(0, count(GameType::Postseason));
+    }
+
+    #[test]
+    fn it_goes_by_name_before_place_and_date() {
+        let at_home = GameDetails {
+            venue: Some("T-Mobile Park - Seattle".to_string()),
+            ..GameDetails::default()
+        };
+
+        assert_eq!(
+            GameType::RegularSeason,
+            classify(
+                NaiveDate::from_ymd_opt(2022, 10, 2),
+                Some("Tigers at Mariners"),
+                &at_home
+            )
+        );
+        assert_eq!(
+            GameType::Postseason,
+            classify(NaiveDate::from_ymd_opt(2019, 11, 1), None, &at_home)
+        );
+        assert_eq!(
+            GameType::Postseason,
+            classify(
+                NaiveDate::from_ymd_opt(2019, 9, 30),
+                Some("Astros at Mariners - ALDS Game 1"),
+                &at_home
+            )
+        );
+        assert_eq!(
+            GameType::RegularSeason,
+            classify(NaiveDate::from_ymd_opt(2019, 3, 28), None, &at_home)
+        );
+        assert_eq!(
+            GameType::SpringTraining,
+            classify(
+                NaiveDate::from_ymd_opt(2019, 2, 28),
+                None,
+                &GameDetails::default()
+            )
+        );
+
+        let in_phoenix = GameDetails {
+            venue: Some("Chase Field - Phoenix".to_string()),
+            ..GameDetails::default()
+        };
+        assert_eq!(
+            GameType::RegularSeason,
+            classify(NaiveDate::from_ymd_opt(2019, 7, 12), None, &in_phoenix)
+        );
+        assert_eq!(
+            GameType::RegularSeason,
+            classify(
+                NaiveDate::from_ymd_opt(2019, 3, 28),
+                Some("Red Sox at D-backs"),
+                &in_phoenix
+            )
+        );
+
+        let in_peoria = GameDetails {
+            venue: Some("Peoria Stadium - Peoria".to_string()),
+            ..GameDetails::default()
+        };
+        assert_eq!(
+            GameType::SpringTraining,
+            classify(NaiveDate::from_ymd_opt(2019, 3, 20), None, &in_peoria)
+        );
+        assert_eq!(
+            GameType::RegularSeason,
+            classify(NaiveDate::from_ymd_opt(2019, 5, 20), None, &in_peoria)
+        );
+    }
+}
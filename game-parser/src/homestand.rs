@@ -0,0 +1,141 @@
+//! Groups a team's games into homestands and road trips, since a week of home games is
+//! easier to plan around than each game on its own.
+
+use std::fmt;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::registry::same_team_name;
+use crate::status::game_day;
+use crate::{Game, GameStatus};
+
+/// More days than this without a game end a stretch, so that a schedule of only home games
+/// still splits where the team went on the road, and the All-Star break splits one too.
+const MAX_DAYS_OFF: i64 = 1;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum StretchKind {
+    Homestand,
+    RoadTrip,
+}
+
+impl fmt::Display for StretchKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StretchKind::Homestand => write!(f, "homestand"),
+            StretchKind::RoadTrip => write!(f, "road trip"),
+        }
+    }
+}
+
+/// A run of games all at home or all away, from the day of the first to the day of the
+/// last, with the opponents in the order they are played.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct Stretch {
+    pub kind: StretchKind,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub opponents: Vec<String>,
+    pub games: usize,
+}
+
+impl fmt::Display for Stretch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} to {}, {} game{} against {}",
+            self.kind,
+            self.start.format("%a %b %-d"),
+            self.end.format("%a %b %-d"),
+            self.games,
+            if self.games == 1 { "" } else { "s" },
+            self.opponents.join(", ")
+        )
+    }
+}
+
+/// Groups `team`'s games by whether it is the home or away team, in day order. A stretch
+/// ends when the team switches between home and away, or goes more than a day without a
+/// game. Games without a day or not involving `team` are left out, as are postponed and
+/// cancelled ones.
+pub fn group_stretches(games: &[Game], team: &str) -> Vec<Stretch> {
+    let mut days = games
+        .iter()
+        .filter(|game| {
+            !matches!(
+                game.details().status,
+                GameStatus::Postponed | GameStatus::Cancelled
+            )
+        })
+        .filter_map(|game| {
+            let details = game.details();
+            let (home, away) = (details.home_team.as_ref()?, details.away_team.as_ref()?);
+            let (kind, opponent) = if same_team_name(home, team) {
+                (StretchKind::Homestand, away)
+            } else if same_team_name(away, team) {
+                (StretchKind::RoadTrip, home)
+            } else {
+                return None;
+            };
+            Some((game_day(game)?, kind, opponent.clone()))
+        })
+        .collect::<Vec<(NaiveDate, StretchKind, String)>>();
+    days.sort_by_key(|(day, _, _)| *day);
+
+    let mut stretches: Vec<Stretch> = vec![];
+    for (day, kind, opponent) in days {
+        match stretches.last_mut() {
+            Some(stretch)
+                if stretch.kind == kind && (day - stretch.end).num_days() <= MAX_DAYS_OFF + 1 =>
+            {
+                stretch.end = day;
+                stretch.games += 1;
+                if stretch.opponents.last() != Some(&opponent) {
+                    stretch.opponents.push(opponent);
+                }
+            }
+            _ => stretches.push(Stretch {
+                kind,
+                start: day,
+                end: day,
+                opponents: vec![opponent],
+                games: 1,
+            }),
+        }
+    }
+    stretches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games_from_csv;
+
+    #[test]
+    fn it_groups_games_into_homestands_and_road_trips() {
+        let games = games_from_csv(
+            "START DATE,START TIME,SUBJECT\r\n\
+             07/12/19,07:10 PM,Angels at Mariners\r\n\
+             07/13/19,06:10 PM,Angels at Mariners\r\n\
+             07/15/19,07:10 PM,Rangers at Mariners\r\n\
+             07/16/19,07:10 PM,Mariners at Athletics\r\n\
+             07/17/19,07:10 PM,Mariners at Athletics - Postponed\r\n\
+             07/22/19,07:10 PM,Red Sox at Mariners\r\n",
+        );
+
+        let stretches = group_stretches(&games, "Seattle Mariners");
+        assert_eq!(
+            vec![
+                "homestand Fri Jul 12 to Mon Jul 15, 3 games against Angels, Rangers",
+                "road trip Tue Jul 16 to Tue Jul 16, 1 game against Athletics",
+                "homestand Mon Jul 22 to Mon Jul 22, 1 game against Red Sox",
+            ],
+            stretches
+                .iter()
+                .map(Stretch::to_string)
+                .collect::<Vec<String>>()
+        );
+        assert!(group_stretches(&games, "Cubs").is_empty());
+    }
+}
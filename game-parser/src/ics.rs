@@ -0,0 +1,332 @@
+//! Reads iCalendar (`.ics`) schedules, such as the subscription feeds MLB publishes, into
+//! the same `Game`s as the CSV export.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::broadcast::parse_broadcasts;
+use crate::columns::{DATE_FORMAT, PACIFIC};
+use crate::dates::{DateFormat, DateFormats};
+use crate::status::{mark_doubleheaders, status_from_text};
+use crate::{
+    from_pacific, game_type, non_empty, parse_matchup, Game, GameDetails, GameStatus, ParseError,
+};
+
+/// A content line after unfolding, e.g. `DTSTART;TZID=America/Los_Angeles:20190328T191000`.
+struct Property {
+    line: usize,
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+enum Moment {
+    Day(NaiveDate),
+    DateTime(DateTime<Utc>),
+}
+
+/// One result per VEVENT, in the order they appear. DTSTART and DTEND may be UTC
+/// (`...Z`), carry a `TZID` from the tz database, be floating (taken as Seattle time) or
+/// be whole days (`VALUE=DATE`), which become `GameWithDay`s whose time is TBD. SUMMARY is
+/// read like the CSV's SUBJECT, "Twins at Mariners", and `STATUS:CANCELLED` events are
+/// kept as cancelled games. Errors name the line DTSTART is on.
+pub fn parse_games_from_ics(contents: &str) -> Vec<Result<Game, ParseError>> {
+    let mut games = vec![];
+    let mut event: Option<Vec<Property>> = None;
+
+    for (line, text) in unfold(contents) {
+        let property = match parse_property(line, &text) {
+            Some(property) => property,
+            None => continue,
+        };
+        match (
+            property.name.as_str(),
+            property.value.to_uppercase().as_str(),
+        ) {
+            ("BEGIN", "VEVENT") => event = Some(vec![]),
+            ("END", "VEVENT") => {
+                if let Some(properties) = event.take() {
+                    games.push(game_from_event(&properties));
+                }
+            }
+            _ => {
+                if let Some(properties) = event.as_mut() {
+                    properties.push(property);
+                }
+            }
+        }
+    }
+
+    mark_doubleheaders(games.iter_mut().filter_map(|game| game.as_mut().ok()));
+    games
+}
+
+/// Joins lines continued with a leading space or tab, keeping the number of the line each
+/// logical line started on.
+fn unfold(contents: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = vec![];
+    for (index, line) in contents.lines().enumerate() {
+        match (line.chars().next(), lines.last_mut()) {
+            (Some(' '), Some((_, previous))) | (Some('\t'), Some((_, previous))) => {
+                previous.push_str(&line[1..])
+            }
+            _ => lines.push((index + 1, line.to_string())),
+        }
+    }
+    lines
+}
+
+fn parse_property(line: usize, text: &str) -> Option<Property> {
+    let mut in_quotes = false;
+    let colon = text.char_indices().find_map(|(index, c)| match c {
+        '"' => {
+            in_quotes = !in_quotes;
+            None
+        }
+        ':' if !in_quotes => Some(index),
+        _ => None,
+    })?;
+    let (head, value) = (&text[..colon], &text[colon + 1..]);
+
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_uppercase();
+    let params = parts
+        .filter_map(|param| {
+            let mut pair = param.splitn(2, '=');
+            let key = pair.next()?.trim().to_uppercase();
+            let value = pair.next()?.trim().trim_matches('"').to_string();
+            Some((key, value))
+        })
+        .collect();
+
+    Some(Property {
+        line,
+        name,
+        params,
+        value: value.to_string(),
+    })
+}
+
+fn game_from_event(properties: &[Property]) -> Result<Game, ParseError> {
+    let get = |name: &str| properties.iter().find(|property| property.name == name);
+    let text = |name: &str| get(name).map(|property| unescape(&property.value));
+
+    let summary = text("SUMMARY");
+    let (away_team, home_team) = match summary.as_ref().map(|summary| parse_matchup(summary)) {
+        Some((away, home)) => (away, home),
+        None => (None, None),
+    };
+    let end_date_time = match get("DTEND").map(parse_moment) {
+        Some(Ok(Moment::DateTime(end))) => Some(end),
+        _ => None,
+    };
+    let start = get("DTSTART").map(parse_moment).transpose()?;
+
+    let mut details = GameDetails {
+        home_team,
+        away_team,
+        venue: text("LOCATION").as_ref().and_then(|venue| non_empty(venue)),
+        end_date_time,
+        description: text("DESCRIPTION")
+            .as_ref()
+            .and_then(|description| non_empty(description)),
+        game_type: Default::default(),
+        status: Default::default(),
+        doubleheader: None,
+        source_id: text("UID").as_ref().and_then(|uid| non_empty(uid)),
+        broadcasts: text("DESCRIPTION")
+            .as_deref()
+            .map(parse_broadcasts)
+            .unwrap_or_default(),
+    };
+    let start_day = match &start {
+        Some(Moment::Day(day)) => Some(*day),
+        Some(Moment::DateTime(date_time)) => {
+            Some(date_time.with_timezone(&PACIFIC).naive_local().date())
+        }
+        None => None,
+    };
+    details.game_type = game_type::classify(start_day, summary.as_deref(), &details);
+    let cancelled =
+        get("STATUS").map(|status| status.value.trim().eq_ignore_ascii_case("CANCELLED"));
+    details.status = if cancelled == Some(true) {
+        GameStatus::Cancelled
+    } else {
+        let texts = summary.iter().chain(details.description.iter());
+        match (status_from_text(texts.map(String::as_str)), &start) {
+            (Some(status), _) => status,
+            (None, Some(Moment::DateTime(_))) => GameStatus::Scheduled,
+            (None, _) => GameStatus::TimeTBD,
+        }
+    };
+
+    Ok(match start {
+        Some(Moment::DateTime(start_date_time)) => Game::PerfectlyScheduledGame {
+            start_date_time,
+            details,
+        },
+        Some(Moment::Day(day)) => Game::GameWithDay {
+            start_day: day.format(DATE_FORMAT).to_string(),
+            details,
+        },
+        None => Game::UnclearGame {
+            start_date_time: None,
+            start_day: None,
+            start_time: None,
+            details,
+        },
+    })
+}
+
+fn parse_moment(property: &Property) -> Result<Moment, ParseError> {
+    let value = property.value.trim();
+    let bad_time = |reason: String| ParseError::BadTime {
+        line: property.line,
+        field: property.name.clone(),
+        value: value.to_string(),
+        reason,
+    };
+
+    if property.param("VALUE") == Some("DATE") || !value.contains('T') {
+        return DateFormats::new(vec![DateFormat::Iso8601])
+            .parse(value)
+            .map(Moment::Day)
+            .map_err(|error| ParseError::BadDate {
+                line: property.line,
+                field: property.name.clone(),
+                value: value.to_string(),
+                reason: error.to_string(),
+            });
+    }
+
+    let naive = NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S")
+        .map_err(|_| bad_time("expected YYYYMMDDTHHMMSS".to_string()))?;
+
+    let date_time = if value.ends_with('Z') {
+        Some(Utc.from_utc_datetime(&naive))
+    } else if let Some(tzid) = property.param("TZID") {
+        let zone = tzid
+            .parse::<Tz>()
+            .map_err(|_| bad_time(format!("{} is not a known time zone", tzid)))?;
+        zone.from_local_datetime(&naive)
+            .earliest()
+            .map(|date_time| date_time.with_timezone(&Utc))
+    } else {
+        from_pacific(naive)
+    };
+
+    date_time
+        .map(Moment::DateTime)
+        .ok_or_else(|| bad_time("clocks skip that hour".to_string()))
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) | ('\\', Some('N')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            ('\\', Some(escaped)) => {
+                unescaped.push(escaped);
+                chars.next();
+            }
+            _ => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameType;
+
+    const FEED: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//MLB//Schedule//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:566083\r\n\
+DTSTART;TZID=\"America/Los_Angeles\":20190328T191000\r\n\
+DTEND;TZID=America/Los_Angeles:20190328T221000\r\n\
+SUMMARY:Red Sox at Mariners\r\n\
+LOCATION:T-Mobile Park\\, Seattle\r\n\
+DESCRIPTION:Local TV: ROOTNW ----- Local Radio: 710 E\r\n \
+ SPN\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART:20190330T021000Z\r\n\
+SUMMARY:Red Sox at Mariners\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;VALUE=DATE:20190709\r\n\
+SUMMARY:NL All-Stars at AL All-Stars - Time TBD\r\n\
+LOCATION:Progressive Field - Cleveland\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;TZID=Pacific/Seattle:20190330T181000\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    #[test]
+    fn it_reads_events_into_games() {
+        let games = parse_games_from_ics(FEED);
+        assert_eq!(4, games.len());
+
+        assert_eq!(
+            Ok(Game::PerfectlyScheduledGame {
+                start_date_time: Utc.ymd(2019, 3, 29).and_hms(2, 10, 0),
+                details: GameDetails {
+                    home_team: Some("Mariners".to_string()),
+                    away_team: Some("Red Sox".to_string()),
+                    venue: Some("T-Mobile Park, Seattle".to_string()),
+                    end_date_time: Some(Utc.ymd(2019, 3, 29).and_hms(5, 10, 0)),
+                    description: Some("Local TV: ROOTNW ----- Local Radio: 710 ESPN".to_string()),
+                    game_type: GameType::RegularSeason,
+                    status: GameStatus::Scheduled,
+                    doubleheader: None,
+                    source_id: Some("566083".to_string()),
+                    broadcasts: parse_broadcasts("Local TV: ROOTNW ----- Local Radio: 710 ESPN"),
+                },
+            }),
+            games[0]
+        );
+        assert_eq!(
+            Some(Utc.ymd(2019, 3, 30).and_hms(2, 10, 0)),
+            match &games[1] {
+                Ok(Game::PerfectlyScheduledGame {
+                    start_date_time, ..
+                }) => Some(*start_date_time),
+                _ => None,
+            }
+        );
+        assert_eq!(
+            Ok(Game::GameWithDay {
+                start_day: "07/09/19".to_string(),
+                details: GameDetails {
+                    home_team: Some("AL All-Stars".to_string()),
+                    away_team: Some("NL All-Stars".to_string()),
+                    venue: Some("Progressive Field - Cleveland".to_string()),
+                    status: GameStatus::TimeTBD,
+                    ..GameDetails::default()
+                },
+            }),
+            games[2]
+        );
+        assert_eq!(
+            "line 23: DTSTART \"20190330T181000\" is not a time: Pacific/Seattle is not a known time zone",
+            games[3].as_ref().unwrap_err().to_string()
+        );
+    }
+}
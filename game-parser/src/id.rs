@@ -0,0 +1,146 @@
+//! A name for each game that stays the same when the schedule moves it to another time.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::homestand::Stretch;
+use crate::status::game_day;
+use crate::Game;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct GameId(String);
+
+impl GameId {
+    pub fn new<S: Into<String>>(id: S) -> GameId {
+        GameId(id.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for GameId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Made from the day and the teams, e.g. "2019-07-20/angels-at-mariners", with "/2" on
+/// for the second game of a doubleheader. Teams go by the last word of their name, so the
+/// CSV's "Red Sox" and the Stats API's "Boston Red Sox" give a game the same ID whichever
+/// schedule it was merged from. The Red Sox and White Sox share "sox", but a team never
+/// plays both on the same day. The schedule's own id (`GameDetails::source_id`) differs
+/// from one schedule to the next, so it is only used to match games up when merging. The
+/// first game gets no number so that adding a makeup game doesn't change it, and the venue
+/// is left out so that a game moved to another park, as when a series is relocated, keeps
+/// its status and texts. `None` when the day or either team is not known.
+pub fn game_id(game: &Game) -> Option<GameId> {
+    let details = game.details();
+    let day = game_day(game)?;
+    let id = format!(
+        "{}/{}-at-{}",
+        day.format("%Y-%m-%d"),
+        team_key(details.away_team.as_ref()?)?,
+        team_key(details.home_team.as_ref()?)?
+    );
+    Some(GameId(match details.doubleheader {
+        Some(number) if number > 1 => format!("{}/{}", id, number),
+        _ => id,
+    }))
+}
+
+/// Made from the first day and the team, e.g. "2019-07-12/mariners-homestand", so that a
+/// text about the whole stretch keeps its status when the schedule is reloaded. `None` when
+/// the team's name has no words in it.
+pub fn stretch_id(team: &str, stretch: &Stretch) -> Option<GameId> {
+    Some(GameId(format!(
+        "{}/{}-{}",
+        stretch.start.format("%Y-%m-%d"),
+        team_key(team)?,
+        stretch.kind.to_string().replace(' ', "-")
+    )))
+}
+
+/// The last word of a team's name, lowercased, skipping a trailing "FC" or "SC" so that
+/// "Seattle Sounders FC" and "Sounders" agree.
+fn team_key(name: &str) -> Option<String> {
+    name.split(|c: char| !c.is_alphanumeric())
+        .rev()
+        .map(str::to_lowercase)
+        .find(|word| !word.is_empty() && word != "fc" && word != "sc")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games_from_csv;
+
+    #[test]
+    fn it_keeps_the_id_when_the_start_time_moves() {
+        let ids = |contents: &str| {
+            games_from_csv(contents)
+                .iter()
+                .map(game_id)
+                .collect::<Vec<Option<GameId>>>()
+        };
+
+        let before = ids("START DATE,START TIME,SUBJECT,LOCATION\r\n\
+             07/20/19,,Angels at Mariners - Time TBD,T-Mobile Park - Seattle\r\n\
+             07/20/19,01:10 PM,Angels at Mariners,T-Mobile Park - Seattle\r\n\
+             07/21/19,01:10 PM,Red Sox at Mariners,T-Mobile Park - Seattle\r\n\
+             07/22/19,01:10 PM,,T-Mobile Park - Seattle\r\n");
+        let after = ids("START DATE,START TIME,SUBJECT,LOCATION\r\n\
+             07/20/19,07:10 PM,Angels at Mariners,T-Mobile Park - Seattle\r\n\
+             07/20/19,12:10 PM,Angels at Mariners,T-Mobile Park - Seattle\r\n\
+             07/21/19,06:40 PM,Red Sox at Mariners,Fenway Park - Boston\r\n\
+             07/22/19,01:10 PM,,T-Mobile Park - Seattle\r\n");
+
+        assert_eq!(
+            vec![
+                Some(GameId::new("2019-07-20/angels-at-mariners/2")),
+                Some(GameId::new("2019-07-20/angels-at-mariners")),
+                Some(GameId::new("2019-07-21/sox-at-mariners")),
+                None,
+            ],
+            before
+        );
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn it_gives_a_game_the_same_id_in_every_schedule() {
+        let mut from_stats_api = games_from_csv(
+            "START DATE,START TIME,SUBJECT\r\n\
+             07/21/19,01:10 PM,Boston Red Sox at Seattle Mariners\r\n",
+        );
+        from_stats_api[0].details_mut().source_id = Some("566083".to_string());
+        let from_export = games_from_csv(
+            "START DATE,START TIME,SUBJECT\r\n07/21/19,01:10 PM,Red Sox at Mariners\r\n",
+        );
+
+        assert_eq!(
+            Some(GameId::new("2019-07-21/sox-at-mariners")),
+            game_id(&from_stats_api[0])
+        );
+        assert_eq!(game_id(&from_stats_api[0]), game_id(&from_export[0]));
+    }
+
+    #[test]
+    fn it_names_a_stretch_by_its_first_day_and_team() {
+        let stretch = Stretch {
+            kind: crate::homestand::StretchKind::RoadTrip,
+            start: chrono::NaiveDate::from_ymd(2019, 7, 12),
+            end: chrono::NaiveDate::from_ymd(2019, 7, 14),
+            opponents: vec!["Angels".to_string()],
+            games: 3,
+        };
+
+        assert_eq!(
+            Some(GameId::new("2019-07-12/mariners-road-trip")),
+            stretch_id("Seattle Mariners", &stretch)
+        );
+        assert_eq!(None, stretch_id("", &stretch));
+    }
+}
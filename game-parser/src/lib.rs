@@ -1,46 +1,265 @@
 use chrono::prelude::*;
 use chrono::Utc;
+use csv_reader::Record;
 use serde::{Deserialize, Serialize};
 
+pub mod broadcast;
+pub mod columns;
+pub mod dates;
+pub mod diff;
+pub mod error;
+pub mod export;
+pub mod filter;
+pub mod game_type;
+pub mod homestand;
+pub mod ics;
+pub mod id;
+pub mod merge;
+pub mod registry;
+pub mod resolve;
+pub mod schema;
+pub mod source;
+pub mod stats_api;
+pub mod status;
+
+pub use error::{DateError, ParseError, SourceError, StatsApiError};
+pub use game_type::GameType;
+pub use id::GameId;
+pub use status::GameStatus;
+
+use broadcast::{parse_broadcasts, Broadcasts};
+use dates::DateFormats;
+
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Game {
     PerfectlyScheduledGame {
         start_date_time: DateTime<Utc>,
+        details: GameDetails,
     },
     GameWithDay {
         start_day: String,
+        details: GameDetails,
     },
     GameWithTime {
         start_time: String,
+        details: GameDetails,
     },
     UnclearGame {
         start_date_time: Option<DateTime<Utc>>,
         start_day: Option<String>,
         start_time: Option<String>,
+        details: GameDetails,
     },
 }
 
-pub fn parse_game_from(csv_row: &[&str]) -> Option<Game> {
-    let start_day = csv_row.get(0).and_then(parse_start_day);
-    let start_time = csv_row.get(1).and_then(parse_start_time);
+/// What the schedule says about a game besides when it starts. Anything missing from the
+/// row is `None`; `game_type` and `status` are worked out from the rest (see
+/// `game_type::classify` and `status::status_from_text`).
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct GameDetails {
+    pub home_team: Option<String>,
+    pub away_team: Option<String>,
+    pub venue: Option<String>,
+    pub end_date_time: Option<DateTime<Utc>>,
+    pub description: Option<String>,
+    pub game_type: GameType,
+    pub status: GameStatus,
+    /// 1 or 2 for the games of a doubleheader.
+    pub doubleheader: Option<u8>,
+    /// The schedule's own id for the game, such as the Stats API's `gamePk` or an
+    /// iCalendar UID, which `merge::merge_sources` matches games up by. It isn't part of
+    /// `id::game_id`, since other schedules don't have it.
+    pub source_id: Option<String>,
+    /// Who is showing the game, read out of `description` (see
+    /// `broadcast::parse_broadcasts`).
+    #[serde(default)]
+    pub broadcasts: Broadcasts,
+}
+
+impl Game {
+    pub fn details(&self) -> &GameDetails {
+        match self {
+            Game::PerfectlyScheduledGame { details, .. }
+            | Game::GameWithDay { details, .. }
+            | Game::GameWithTime { details, .. }
+            | Game::UnclearGame { details, .. } => details,
+        }
+    }
+
+    pub fn details_mut(&mut self) -> &mut GameDetails {
+        match self {
+            Game::PerfectlyScheduledGame { details, .. }
+            | Game::GameWithDay { details, .. }
+            | Game::GameWithTime { details, .. }
+            | Game::UnclearGame { details, .. } => details,
+        }
+    }
+}
+
+/// Parses every record like `parse_game_from`, then numbers the games of doubleheaders
+/// (see `status::mark_doubleheaders`).
+pub fn parse_games_from(records: &[Record]) -> Vec<Result<Game, ParseError>> {
+    parse_games_with(records, &DateFormats::default())
+}
+
+/// `parse_games_from` for a schedule whose dates are written in `date_formats`.
+pub fn parse_games_with(
+    records: &[Record],
+    date_formats: &DateFormats,
+) -> Vec<Result<Game, ParseError>> {
+    let mut games = records
+        .iter()
+        .map(|record| parse_game_with(record, date_formats))
+        .collect::<Vec<Result<Game, ParseError>>>();
+    status::mark_doubleheaders(games.iter_mut().filter_map(|game| game.as_mut().ok()));
+    games
+}
+
+/// Rows without a start date or time (blank or "TBD") still parse, into the less
+/// complete `Game` variants. A start date or time that is there but malformed is an error.
+pub fn parse_game_from(record: &Record) -> Result<Game, ParseError> {
+    parse_game_with(record, &DateFormats::default())
+}
+
+/// `parse_game_from` for a schedule whose dates are written in `date_formats`. The day of
+/// a `GameWithDay` is rewritten as `columns::DATE_FORMAT` whichever way it was written.
+pub fn parse_game_with(record: &Record, date_formats: &DateFormats) -> Result<Game, ParseError> {
+    let start_day = record.get(columns::START_DATE).and_then(parse_start_day);
+    let start_time = record.get(columns::START_TIME).and_then(parse_start_time);
+    let details = parse_details_with(record, date_formats);
 
     match (&start_day, &start_time) {
-        (Some(date), Some(time)) => create_perfect_game(date, time),
-        (Some(day), _) => Some(Game::GameWithDay {
-            start_day: day.to_string(),
-        }),
-        (_, Some(t)) => Some(Game::GameWithTime {
-            start_time: t.to_string(),
-        }),
-        _ => Some(Game::UnclearGame {
+        (Some(_), Some(_)) => {
+            let start_date_time = parse_date_time(
+                record,
+                columns::START_DATE,
+                columns::START_TIME,
+                date_formats,
+            )?;
+            if agrees_with_eastern_time(record, start_date_time) {
+                Ok(Game::PerfectlyScheduledGame {
+                    start_date_time,
+                    details,
+                })
+            } else {
+                Ok(Game::UnclearGame {
+                    start_date_time: Some(start_date_time),
+                    start_day,
+                    start_time,
+                    details,
+                })
+            }
+        }
+        (Some(_), _) => {
+            let day = parse_date(record, columns::START_DATE, date_formats)?;
+            Ok(Game::GameWithDay {
+                start_day: day.format(columns::DATE_FORMAT).to_string(),
+                details,
+            })
+        }
+        (_, Some(t)) => {
+            parse_time(record, columns::START_TIME)?;
+            Ok(Game::GameWithTime {
+                start_time: t.to_string(),
+                details,
+            })
+        }
+        _ => Ok(Game::UnclearGame {
             start_day,
             start_time: start_time.map(|x| x.to_string()),
             start_date_time: None,
+            details,
         }),
     }
 }
 
-fn parse_start_day(c: &&str) -> Option<String> {
+/// Reads the matchup out of SUBJECT, which the MLB export writes as "Twins at Mariners",
+/// sometimes followed by a note such as " - Time TBD".
+pub fn parse_details(record: &Record) -> GameDetails {
+    parse_details_with(record, &DateFormats::default())
+}
+
+pub(crate) fn parse_details_with(record: &Record, date_formats: &DateFormats) -> GameDetails {
+    let (away_team, home_team) = match record.get(columns::SUBJECT).map(parse_matchup) {
+        Some((away, home)) => (away, home),
+        None => (None, None),
+    };
+    let end_date_time = match (
+        record.get(columns::END_DATE).and_then(parse_start_day),
+        record.get(columns::END_TIME).and_then(parse_start_time),
+    ) {
+        (Some(_), Some(_)) => {
+            parse_date_time(record, columns::END_DATE, columns::END_TIME, date_formats).ok()
+        }
+        _ => None,
+    };
+
+    let mut details = GameDetails {
+        home_team,
+        away_team,
+        venue: record.get(columns::LOCATION).and_then(non_empty),
+        end_date_time,
+        description: record.get(columns::DESCRIPTION).and_then(non_empty),
+        game_type: GameType::default(),
+        status: GameStatus::default(),
+        doubleheader: None,
+        source_id: None,
+        broadcasts: record
+            .get(columns::DESCRIPTION)
+            .map(parse_broadcasts)
+            .unwrap_or_default(),
+    };
+    let has_start_time = record
+        .get(columns::START_TIME)
+        .and_then(parse_start_time)
+        .is_some();
+    details.status = status::status_from_text(
+        record
+            .get(columns::SUBJECT)
+            .into_iter()
+            .chain(record.get(columns::DESCRIPTION)),
+    )
+    .unwrap_or(if has_start_time {
+        GameStatus::Scheduled
+    } else {
+        GameStatus::TimeTBD
+    });
+    details.game_type = game_type::classify(
+        parse_date(record, columns::START_DATE, date_formats).ok(),
+        record.get(columns::SUBJECT),
+        &details,
+    );
+    details
+}
+
+pub(crate) fn parse_matchup(subject: &str) -> (Option<String>, Option<String>) {
+    let without_note = |team: &str| team.split(" - ").next().unwrap_or(team).to_string();
+    if let Some((away, home)) = split_once(subject, " at ") {
+        return (non_empty(away), non_empty(&without_note(home)));
+    }
+    for separator in &[" vs. ", " vs ", " v "] {
+        if let Some((home, away)) = split_once(subject, separator) {
+            return (non_empty(&without_note(away)), non_empty(home));
+        }
+    }
+    (None, None)
+}
+
+fn split_once<'a>(text: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    let mut parts = text.splitn(2, separator);
+    Some((parts.next()?, parts.next()?))
+}
+
+pub(crate) fn non_empty(c: &str) -> Option<String> {
+    let c = c.trim();
+    if c.is_empty() {
+        None
+    } else {
+        Some(c.to_string())
+    }
+}
+
+fn parse_start_day(c: &str) -> Option<String> {
     if contains_number_like_chars(c) {
         Some(c.to_string())
     } else {
@@ -48,7 +267,7 @@ fn parse_start_day(c: &&str) -> Option<String> {
     }
 }
 
-fn parse_start_time(c: &&str) -> Option<String> {
+fn parse_start_time(c: &str) -> Option<String> {
     if contains_number_like_chars(c) {
         Some(c.to_string())
     } else {
@@ -60,66 +279,134 @@ fn contains_number_like_chars(c: &str) -> bool {
     c.chars().any(char::is_numeric)
 }
 
-fn create_perfect_game(date_str: &str, time_str: &str) -> Option<Game> {
-    let iso_8061_ts_start = transform_mariners_date(date_str);
-
-    let iso_8061_ts_end = transform_mariners_time(time_str);
+/// The instant a Seattle wall-clock time refers to. Times skipped when clocks spring
+/// forward do not exist; times repeated when they fall back resolve to the earlier one.
+pub fn from_pacific(date_time: NaiveDateTime) -> Option<DateTime<Utc>> {
+    columns::PACIFIC
+        .from_local_datetime(&date_time)
+        .earliest()
+        .map(|date_time| date_time.with_timezone(&Utc))
+}
 
-    let date_time = iso_8061_ts_start + "T" + &iso_8061_ts_end;
+pub(crate) fn parse_date_time(
+    record: &Record,
+    date_field: &str,
+    time_field: &str,
+    date_formats: &DateFormats,
+) -> Result<DateTime<Utc>, ParseError> {
+    let date = parse_date(record, date_field, date_formats)?;
+    let time = parse_time(record, time_field)?;
 
-    date_time
-        .parse::<DateTime<Utc>>()
-        .ok()
-        .map(|dt| Game::PerfectlyScheduledGame {
-            start_date_time: dt,
-        })
+    from_pacific(date.and_time(time)).ok_or_else(|| ParseError::BadTime {
+        line: record.line(),
+        field: time_field.to_string(),
+        value: record.get(time_field).unwrap_or_default().to_string(),
+        reason: "clocks in Seattle skip that hour".to_string(),
+    })
 }
 
-fn transform_mariners_date(date_str: &str) -> String {
-    let date_parts: Vec<&str> = date_str.split('/').collect();
-    match date_parts.as_slice() {
-        [month, day, year] => {
-            ["20".to_owned() + year, month.to_string(), day.to_string()].join("-")
-        }
-        _ => date_str.to_owned(),
-    }
-    .replace("/", "-")
+/// Reads the date in `field` with `date_formats`.
+pub(crate) fn parse_date(
+    record: &Record,
+    field: &str,
+    date_formats: &DateFormats,
+) -> Result<NaiveDate, ParseError> {
+    let value = record.get(field).unwrap_or_default();
+    date_formats.parse(value).map_err(|error| match error {
+        DateError::Ambiguous { readings } => ParseError::AmbiguousDate {
+            line: record.line(),
+            field: field.to_string(),
+            value: value.to_string(),
+            readings,
+        },
+        error => ParseError::BadDate {
+            line: record.line(),
+            field: field.to_string(),
+            value: value.to_string(),
+            reason: error.to_string(),
+        },
+    })
 }
 
-fn transform_mariners_time(time_str: &str) -> String {
-    let time_and_meridiem: Vec<&str> = time_str.split(' ').collect();
+/// Reads a 12-hour `HH:MM AM` time, with or without the space before the meridiem.
+pub(crate) fn parse_time(record: &Record, field: &str) -> Result<NaiveTime, ParseError> {
+    let value = record.get(field).unwrap_or_default();
+    let bad_time = |reason: &str| ParseError::BadTime {
+        line: record.line(),
+        field: field.to_string(),
+        value: value.to_string(),
+        reason: reason.to_string(),
+    };
 
-    let time = time_and_meridiem[0];
-    let meridiem = time_and_meridiem[1];
+    let trimmed = value.trim();
+    let (clock, meridiem) =
+        trimmed.split_at(trimmed.find(char::is_alphabetic).unwrap_or(trimmed.len()));
 
-    let time_pieces: Vec<u8> = time
-        .split(':')
-        .map(|x| {
-            let f = x.parse::<u8>();
-            f.unwrap()
-        })
-        .collect();
+    let mut clock_parts = clock.trim().split(':').map(|part| part.parse::<u32>());
+    let (hour, minute) = match (clock_parts.next(), clock_parts.next(), clock_parts.next()) {
+        (Some(Ok(hour)), Some(Ok(minute)), None) => (hour, minute),
+        _ => return Err(bad_time("expected HH:MM")),
+    };
 
-    let hour = time_pieces[0];
-    let minute = time_pieces[1];
+    let is_pm = match meridiem.to_uppercase().as_str() {
+        "AM" => false,
+        "PM" => true,
+        _ => {
+            return Err(ParseError::BadMeridiem {
+                line: record.line(),
+                field: field.to_string(),
+                value: value.to_string(),
+            })
+        }
+    };
+    if !(1..=12).contains(&hour) {
+        return Err(ParseError::HourOutOfRange {
+            line: record.line(),
+            field: field.to_string(),
+            value: value.to_string(),
+            hour,
+        });
+    }
 
-    let hours_in_24_hr_format = from_12_hr_fmt_to_24_hr_ft(hour, meridiem);
+    let hour = hour % 12 + if is_pm { 12 } else { 0 };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+        .ok_or_else(|| bad_time("the minutes are not between 00 and 59"))
+}
 
-    hours_in_24_hr_format + ":" + &minute.to_string() + ":00.0000000-0700"
+/// Whether the START TIME ET column, when it holds a time, is the same moment as
+/// `start_date_time`. The export has no eastern start date, so only the times are compared.
+pub(crate) fn agrees_with_eastern_time(record: &Record, start_date_time: DateTime<Utc>) -> bool {
+    match parse_time(record, columns::START_TIME_ET) {
+        Ok(eastern) => start_date_time.with_timezone(&columns::EASTERN).time() == eastern,
+        Err(_) => true,
+    }
 }
 
-fn from_12_hr_fmt_to_24_hr_ft(hour: u8, meridian: &str) -> String {
-    (hour
-        + match meridian {
-            "PM" => 12,
-            _ => 0,
-        })
-    .to_string()
+/// Parses a CSV schedule written out in a test, every row of which is expected to parse.
+#[cfg(test)]
+pub(crate) fn games_from_csv(contents: &str) -> Vec<Game> {
+    parse_games_from(&csv_reader::read_records(contents))
+        .into_iter()
+        .map(Result::unwrap)
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dates::DateFormat;
+    use std::sync::Arc;
+
+    fn record(fields: &[&str]) -> Record {
+        Record::new(
+            2,
+            Arc::new(vec![
+                columns::START_DATE.to_string(),
+                columns::START_TIME.to_string(),
+            ]),
+            fields.iter().map(|f| f.to_string()).collect(),
+        )
+    }
 
     #[test]
     fn it_works() {
@@ -128,26 +415,199 @@ mod tests {
             "2015-05-15T00:00:00Z".parse::<DateTime<Utc>>()
         );
 
-        let r = parse_game_from(&vec!["11/26/19", "11:30 PM"]);
+        let r = parse_game_from(&record(&["11/26/19", "11:30 PM"]));
         assert_eq!(
-            Some(Game::PerfectlyScheduledGame {
-                start_date_time: Utc.ymd(2019, 11, 27).and_hms(6, 30, 0)
+            Ok(Game::PerfectlyScheduledGame {
+                start_date_time: Utc.ymd(2019, 11, 27).and_hms(7, 30, 0),
+                details: GameDetails {
+                    game_type: GameType::Postseason,
+                    ..GameDetails::default()
+                },
             }),
             r
         );
 
         assert_eq!(
-            Some(Game::GameWithDay {
-                start_day: "11/26/19".to_string()
+            Ok(Game::GameWithDay {
+                start_day: "11/26/19".to_string(),
+                details: GameDetails {
+                    game_type: GameType::Postseason,
+                    status: GameStatus::TimeTBD,
+                    ..GameDetails::default()
+                },
             }),
-            parse_game_from(&vec!["11/26/19"])
+            parse_game_from(&record(&["11/26/19"]))
         );
 
         assert_eq!(
-            Some(Game::GameWithTime {
-                start_time: "11:23 PM".to_string()
+            Ok(Game::GameWithTime {
+                start_time: "11:23 PM".to_string(),
+                details: GameDetails::default(),
+            }),
+            parse_game_from(&record(&["", "11:23 PM"]))
+        );
+    }
+    #[test]
+    fn it_reads_columns_by_header_name() {
+        let records = csv_reader::read_records(
+            "SUBJECT,START TIME,START DATE\r\nRed Sox at Mariners,04:10 PM,03/28/19\r\n",
+        );
+
+        assert_eq!(1, records.len());
+        assert_eq!(
+            Ok(Game::PerfectlyScheduledGame {
+                start_date_time: Utc.ymd(2019, 3, 28).and_hms(23, 10, 0),
+                details: GameDetails {
+                    home_team: Some("Mariners".to_string()),
+                    away_team: Some("Red Sox".to_string()),
+                    ..GameDetails::default()
+                },
+            }),
+            parse_game_from(&records[0])
+        );
+    }
+
+    #[test]
+    fn it_keeps_who_is_playing_where_and_until_when() {
+        let records = csv_reader::read_records(
+            "START DATE,START TIME,SUBJECT,LOCATION,DESCRIPTION,END DATE,END TIME\r\n\
+             03/28/19,04:10 PM,Red Sox at Mariners,T-Mobile Park - Seattle,\"Local TV: ROOTNW ----- Local Radio: 710 ESPN\",03/28/19,07:10 PM\r\n\
+             07/09/19,,NL All-Stars at AL All-Stars - Time TBD,Progressive Field - Cleveland,\"\",07/09/19,\r\n",
+        );
+
+        assert_eq!(
+            Ok(Game::PerfectlyScheduledGame {
+                start_date_time: Utc.ymd(2019, 3, 28).and_hms(23, 10, 0),
+                details: GameDetails {
+                    home_team: Some("Mariners".to_string()),
+                    away_team: Some("Red Sox".to_string()),
+                    venue: Some("T-Mobile Park - Seattle".to_string()),
+                    end_date_time: Some(Utc.ymd(2019, 3, 29).and_hms(2, 10, 0)),
+                    description: Some("Local TV: ROOTNW ----- Local Radio: 710 ESPN".to_string()),
+                    game_type: GameType::RegularSeason,
+                    status: GameStatus::Scheduled,
+                    doubleheader: None,
+                    source_id: None,
+                    broadcasts: parse_broadcasts("Local TV: ROOTNW ----- Local Radio: 710 ESPN"),
+                }
+            }),
+            parse_game_from(&records[0])
+        );
+        assert_eq!(
+            GameDetails {
+                home_team: Some("AL All-Stars".to_string()),
+                away_team: Some("NL All-Stars".to_string()),
+                venue: Some("Progressive Field - Cleveland".to_string()),
+                end_date_time: None,
+                description: None,
+                game_type: GameType::RegularSeason,
+                status: GameStatus::TimeTBD,
+                doubleheader: None,
+                source_id: None,
+                broadcasts: Broadcasts::default(),
+            },
+            parse_details(&records[1])
+        );
+    }
+
+    #[test]
+    fn it_follows_daylight_saving_time_and_checks_the_eastern_start() {
+        let records = csv_reader::read_records(
+            "START DATE,START TIME,START TIME ET\r\n\
+             03/08/19,07:10 PM,10:10 PM\r\n\
+             03/28/19,07:10 PM,10:10 PM\r\n\
+             05/20/19,9:31 PM,03:10 PM\r\n",
+        );
+        let games = records
+            .iter()
+            .map(|record| parse_game_from(record).unwrap())
+            .collect::<Vec<Game>>();
+
+        assert_eq!(
+            Game::PerfectlyScheduledGame {
+                start_date_time: Utc.ymd(2019, 3, 9).and_hms(3, 10, 0),
+                details: GameDetails::default(),
+            },
+            games[0]
+        );
+        assert_eq!(
+            Game::PerfectlyScheduledGame {
+                start_date_time: Utc.ymd(2019, 3, 29).and_hms(2, 10, 0),
+                details: GameDetails::default(),
+            },
+            games[1]
+        );
+        assert_eq!(
+            Game::UnclearGame {
+                start_date_time: Some(Utc.ymd(2019, 5, 21).and_hms(4, 31, 0)),
+                start_day: Some("05/20/19".to_string()),
+                start_time: Some("9:31 PM".to_string()),
+                details: GameDetails::default(),
+            },
+            games[2]
+        );
+    }
+
+    #[test]
+    fn it_handles_noon_and_midnight() {
+        let start = |time: &str| match parse_game_from(&record(&["07/04/19", time])) {
+            Ok(Game::PerfectlyScheduledGame {
+                start_date_time, ..
+            }) => start_date_time,
+            other => panic!("{:?}", other),
+        };
+
+        assert_eq!(Utc.ymd(2019, 7, 4).and_hms(19, 40, 0), start("12:40 PM"));
+        assert_eq!(Utc.ymd(2019, 7, 4).and_hms(7, 40, 0), start("12:40 AM"));
+        assert_eq!(Utc.ymd(2019, 7, 5).and_hms(2, 10, 0), start("7:10PM"));
+    }
+
+    #[test]
+    fn it_explains_which_field_failed_and_why() {
+        let error = |fields: &[&str]| parse_game_from(&record(fields)).unwrap_err().to_string();
+
+        assert_eq!(
+            "line 2: START DATE \"07-04-19\" is not a date: expected MM/DD/YY, YYYY-MM-DD or a date like March 28, 2019",
+            error(&["07-04-19", "07:10 PM"])
+        );
+        assert_eq!(
+            "line 2: START DATE \"02/30/19\" is not a date: there is no such day",
+            error(&["02/30/19"])
+        );
+        assert_eq!(
+            "line 2: START DATE \"03/04/19\" is ambiguous: it could be Mon Mar 4 2019 or Wed Apr 3 2019",
+            parse_game_with(
+                &record(&["03/04/19"]),
+                &DateFormats::new(vec![DateFormat::MonthDayYear, DateFormat::DayMonthYear])
+            )
+            .unwrap_err()
+            .to_string()
+        );
+        assert_eq!(
+            "line 2: START TIME \"7 PM\" is not a time: expected HH:MM",
+            error(&["07/04/19", "7 PM"])
+        );
+        assert_eq!(
+            "line 2: START TIME \"19:10\" does not end in AM or PM",
+            error(&["07/04/19", "19:10"])
+        );
+        assert_eq!(
+            "line 2: START TIME \"13:10 PM\" has hour 13, which is not between 1 and 12",
+            error(&["", "13:10 PM"])
+        );
+        assert_eq!(
+            "line 2: START TIME \"02:30 AM\" is not a time: clocks in Seattle skip that hour",
+            error(&["03/10/19", "02:30 AM"])
+        );
+        assert_eq!(
+            Ok(Game::GameWithDay {
+                start_day: "07/09/19".to_string(),
+                details: GameDetails {
+                    status: GameStatus::TimeTBD,
+                    ..GameDetails::default()
+                },
             }),
-            parse_game_from(&vec!["", "11:23 PM"])
+            parse_game_from(&record(&["07/09/19", "TBD"]))
         );
     }
 }
@@ -0,0 +1,312 @@
+//! Combines the games of several schedules, such as the CSV export, an iCalendar feed and
+//! a file of manual overrides, into one, keeping track of which schedule each field came
+//! from.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::registry::same_team_name;
+use crate::status::game_day;
+use crate::{Game, GameDetails, GameStatus, GameType};
+
+/// A game as merged from every schedule that has it.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct MergedGame {
+    pub game: Game,
+    pub provenance: Provenance,
+}
+
+/// The schedules that set each field, in the order they were applied, so the last one is
+/// where the value came from.
+#[derive(Debug, PartialEq, Clone, Default, Serialize)]
+pub struct Provenance {
+    pub start: Vec<String>,
+    pub end: Vec<String>,
+    pub venue: Vec<String>,
+    pub status: Vec<String>,
+}
+
+impl fmt::Display for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields = [
+            ("time", &self.start),
+            ("end", &self.end),
+            ("venue", &self.venue),
+            ("status", &self.status),
+        ];
+        let described = fields
+            .iter()
+            .filter_map(|(field, sources)| {
+                let (first, overrides) = sources.split_first()?;
+                let mut described = format!("{} from {}", field, first);
+                for source in overrides {
+                    described.push_str(&format!(", overridden by {}", source));
+                }
+                Some(described)
+            })
+            .collect::<Vec<String>>();
+        write!(f, "{}", described.join("; "))
+    }
+}
+
+/// Merges `sources`, named schedules listed from lowest to highest priority. A game is the
+/// same game in two schedules when they give it the same id, or when it is on the same
+/// day between the same teams, allowing for one schedule saying "Red Sox" where another
+/// says "Boston Red Sox". Where schedules disagree the higher priority one wins, though a
+/// field one leaves blank doesn't undo another's. Games that can't be matched up, because
+/// their day isn't known, are kept as they are.
+pub fn merge_sources<S: AsRef<str>>(sources: &[(S, Vec<Game>)]) -> Vec<MergedGame> {
+    let mut merged: Vec<MergedGame> = vec![];
+    for (name, games) in sources {
+        let name = name.as_ref();
+        for game in games {
+            match merged
+                .iter_mut()
+                .find(|merged| is_same_game(&merged.game, game))
+            {
+                Some(merged) => apply(merged, game, name),
+                None => {
+                    let mut provenance = Provenance::default();
+                    record(&mut provenance, &blank_game(), game, name);
+                    merged.push(MergedGame {
+                        game: game.clone(),
+                        provenance,
+                    });
+                }
+            }
+        }
+    }
+    merged
+}
+
+/// A game the schedule says nothing about, which the first schedule to have a game is
+/// compared against.
+fn blank_game() -> Game {
+    Game::UnclearGame {
+        start_date_time: None,
+        start_day: None,
+        start_time: None,
+        details: GameDetails::default(),
+    }
+}
+
+fn is_same_game(a: &Game, b: &Game) -> bool {
+    let (a_details, b_details) = (a.details(), b.details());
+    if let (Some(a_id), Some(b_id)) = (&a_details.source_id, &b_details.source_id) {
+        if a_id == b_id {
+            return true;
+        }
+    }
+
+    let same_team = |a: &Option<String>, b: &Option<String>| match (a, b) {
+        (Some(a), Some(b)) => same_team_name(a, b),
+        _ => false,
+    };
+    let day = game_day(a);
+    day.is_some()
+        && day == game_day(b)
+        && same_team(&a_details.home_team, &b_details.home_team)
+        && same_team(&a_details.away_team, &b_details.away_team)
+        && a_details.doubleheader.unwrap_or(1) == b_details.doubleheader.unwrap_or(1)
+}
+
+/// A game with only a day keeps the start time it already has on that day, along with
+/// the status that goes with it, since its own TBD status only says its schedule has no
+/// time. `game_type` is only taken when the schedule says it is something other than
+/// regular season, which is what `game_type::classify` falls back to when it has nothing
+/// to go on.
+fn apply(merged: &mut MergedGame, game: &Game, name: &str) {
+    let before = merged.game.clone();
+    let keeps_start = match (&merged.game, game) {
+        (Game::PerfectlyScheduledGame { .. }, Game::PerfectlyScheduledGame { .. }) => false,
+        (Game::PerfectlyScheduledGame { .. }, _) => {
+            game_day(game).is_none() || game_day(game) == game_day(&merged.game)
+        }
+        _ => false,
+    };
+    let details = {
+        let current = merged.game.details();
+        let new = game.details();
+        GameDetails {
+            home_team: new.home_team.clone().or_else(|| current.home_team.clone()),
+            away_team: new.away_team.clone().or_else(|| current.away_team.clone()),
+            venue: new.venue.clone().or_else(|| current.venue.clone()),
+            end_date_time: new.end_date_time.or(current.end_date_time),
+            description: new
+                .description
+                .clone()
+                .or_else(|| current.description.clone()),
+            game_type: if new.game_type == GameType::RegularSeason {
+                current.game_type
+            } else {
+                new.game_type
+            },
+            status: if keeps_start && new.status == GameStatus::TimeTBD {
+                current.status
+            } else {
+                new.status
+            },
+            doubleheader: new.doubleheader.or(current.doubleheader),
+            source_id: current.source_id.clone().or_else(|| new.source_id.clone()),
+            broadcasts: if new.broadcasts.is_empty() {
+                current.broadcasts.clone()
+            } else {
+                new.broadcasts.clone()
+            },
+        }
+    };
+
+    merged.game = match game {
+        _ if keeps_start => {
+            let mut game = merged.game.clone();
+            *game.details_mut() = details;
+            game
+        }
+        Game::PerfectlyScheduledGame {
+            start_date_time, ..
+        } => Game::PerfectlyScheduledGame {
+            start_date_time: *start_date_time,
+            details,
+        },
+        Game::GameWithDay { start_day, .. } => Game::GameWithDay {
+            start_day: start_day.clone(),
+            details,
+        },
+        _ => {
+            let mut game = merged.game.clone();
+            *game.details_mut() = details;
+            game
+        }
+    };
+    record(&mut merged.provenance, &before, &merged.game, name);
+}
+
+/// Adds `name` to the fields it changed going from `before` to `after`. Every game has a
+/// status, so the first schedule to have the game always sets it.
+fn record(provenance: &mut Provenance, before: &Game, after: &Game, name: &str) {
+    let start = |game: &Game| match game {
+        Game::PerfectlyScheduledGame {
+            start_date_time, ..
+        } => Some(start_date_time.to_rfc3339()),
+        Game::GameWithDay { start_day, .. } => Some(start_day.clone()),
+        _ => None,
+    };
+    let (old, new) = (before.details(), after.details());
+    let first_status = provenance.status.is_empty();
+
+    let fields = [
+        (&mut provenance.start, start(before) != start(after)),
+        (&mut provenance.end, old.end_date_time != new.end_date_time),
+        (&mut provenance.venue, old.venue != new.venue),
+        (
+            &mut provenance.status,
+            old.status != new.status || first_status,
+        ),
+    ];
+    for (sources, changed) in fields {
+        if changed {
+            sources.push(name.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games_from_csv as csv;
+    use crate::ics::parse_games_from_ics;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn it_merges_the_same_game_from_several_schedules() {
+        let export = csv("START DATE,START TIME,SUBJECT,LOCATION\r\n\
+             03/28/19,07:10 PM,Red Sox at Mariners,T-Mobile Park - Seattle\r\n\
+             03/29/19,,Red Sox at Mariners - Time TBD,T-Mobile Park - Seattle\r\n");
+        let feed = parse_games_from_ics(
+            "BEGIN:VCALENDAR\r\n\
+BEGIN:VEVENT\r\n\
+UID:566084\r\n\
+DTSTART;TZID=America/Los_Angeles:20190329T191000\r\n\
+SUMMARY:Boston Red Sox at Seattle Mariners\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;TZID=America/Los_Angeles:20190330T181000\r\n\
+SUMMARY:Boston Red Sox at Seattle Mariners\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n",
+        )
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+        let overrides = csv("START DATE,START TIME,SUBJECT\r\n\
+             03/29/19,06:40 PM,Red Sox at Mariners\r\n");
+
+        let merged = merge_sources(&[
+            ("the CSV export", export),
+            ("the iCalendar feed", feed),
+            ("manual overrides", overrides),
+        ]);
+
+        assert_eq!(3, merged.len());
+        assert_eq!(
+            "time from the CSV export; venue from the CSV export; status from the CSV export",
+            merged[0].provenance.to_string()
+        );
+        assert_eq!(
+            Game::PerfectlyScheduledGame {
+                start_date_time: Utc.ymd(2019, 3, 30).and_hms(1, 40, 0),
+                details: GameDetails {
+                    home_team: Some("Mariners".to_string()),
+                    away_team: Some("Red Sox".to_string()),
+                    venue: Some("T-Mobile Park - Seattle".to_string()),
+                    status: GameStatus::Scheduled,
+                    source_id: Some("566084".to_string()),
+                    ..GameDetails::default()
+                },
+            },
+            merged[1].game
+        );
+        assert_eq!(
+            "time from the CSV export, overridden by the iCalendar feed, overridden by manual overrides; \
+             venue from the CSV export; \
+             status from the CSV export, overridden by the iCalendar feed",
+            merged[1].provenance.to_string()
+        );
+        assert_eq!(
+            Some("Boston Red Sox".to_string()),
+            merged[2].game.details().away_team
+        );
+    }
+
+    #[test]
+    fn it_keeps_the_start_time_when_an_override_only_gives_the_day() {
+        let export = csv("START DATE,START TIME,SUBJECT,LOCATION\r\n\
+             07/09/19,07:10 PM,Twins at Mariners,T-Mobile Park - Seattle\r\n");
+        let overrides = csv("START DATE,SUBJECT,LOCATION\r\n\
+             07/09/19,Twins at Mariners,Safeco Field - Seattle\r\n");
+
+        let merged = merge_sources(&[("the CSV export", export), ("manual overrides", overrides)]);
+
+        assert_eq!(
+            Game::PerfectlyScheduledGame {
+                start_date_time: Utc.ymd(2019, 7, 10).and_hms(2, 10, 0),
+                details: GameDetails {
+                    home_team: Some("Mariners".to_string()),
+                    away_team: Some("Twins".to_string()),
+                    venue: Some("Safeco Field - Seattle".to_string()),
+                    status: GameStatus::Scheduled,
+                    game_type: GameType::RegularSeason,
+                    ..GameDetails::default()
+                },
+            },
+            merged[0].game
+        );
+        assert_eq!(
+            "time from the CSV export; \
+             venue from the CSV export, overridden by manual overrides; \
+             status from the CSV export",
+            merged[0].provenance.to_string()
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! The teams and venues schedules are loaded for, so games of several teams, and of sports
+//! other than baseball, can be told apart and named properly.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Game, GameType};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sport {
+    #[default]
+    Baseball,
+    Football,
+    Soccer,
+    Other,
+}
+
+impl fmt::Display for Sport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sport::Baseball => write!(f, "baseball"),
+            Sport::Football => write!(f, "football"),
+            Sport::Soccer => write!(f, "soccer"),
+            Sport::Other => write!(f, "other"),
+        }
+    }
+}
+
+/// A team, found in schedules by its name or any of its aliases, e.g. "Mariners" or
+/// "Seattle Mariners".
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Team {
+    pub name: String,
+    #[serde(default)]
+    pub sport: Sport,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// The name of the team's home `Venue`.
+    pub venue: String,
+}
+
+/// A venue, found in schedules by its name or any of its aliases, with or without the city
+/// after it ("T-Mobile Park - Seattle").
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Venue {
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Registry {
+    teams: Vec<Team>,
+    venues: Vec<Venue>,
+}
+
+impl Registry {
+    pub fn new(teams: Vec<Team>, venues: Vec<Venue>) -> Registry {
+        Registry { teams, venues }
+    }
+
+    /// The teams whose games fill SODO: the Mariners at T-Mobile Park, and the Seahawks and
+    /// Sounders at Lumen Field.
+    pub fn sodo() -> Registry {
+        let strings = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        Registry::new(
+            vec![
+                Team {
+                    name: "Mariners".to_string(),
+                    sport: Sport::Baseball,
+                    aliases: strings(&["Seattle Mariners", "M's"]),
+                    venue: "T-Mobile Park".to_string(),
+                },
+                Team {
+                    name: "Seahawks".to_string(),
+                    sport: Sport::Football,
+                    aliases: strings(&["Seattle Seahawks"]),
+                    venue: "Lumen Field".to_string(),
+                },
+                Team {
+                    name: "Sounders".to_string(),
+                    sport: Sport::Soccer,
+                    aliases: strings(&["Seattle Sounders", "Seattle Sounders FC", "Sounders FC"]),
+                    venue: "Lumen Field".to_string(),
+                },
+            ],
+            vec![
+                Venue {
+                    name: "T-Mobile Park".to_string(),
+                    aliases: strings(&["Safeco Field"]),
+                },
+                Venue {
+                    name: "Lumen Field".to_string(),
+                    aliases: strings(&["CenturyLink Field"]),
+                },
+            ],
+        )
+    }
+
+    pub fn teams(&self) -> &[Team] {
+        &self.teams
+    }
+
+    pub fn venues(&self) -> &[Venue] {
+        &self.venues
+    }
+
+    pub fn team(&self, name: &str) -> Option<&Team> {
+        self.teams.iter().find(|team| {
+            std::iter::once(&team.name)
+                .chain(&team.aliases)
+                .any(|known| same_team_name(known, name))
+        })
+    }
+
+    pub fn venue(&self, name: &str) -> Option<&Venue> {
+        let name = name.trim().to_lowercase();
+        let stadium = name
+            .split(" - ")
+            .next()
+            .and_then(|name| name.split(',').next())
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        self.venues.iter().find(|venue| {
+            std::iter::once(&venue.name)
+                .chain(&venue.aliases)
+                .map(|known| known.trim().to_lowercase())
+                .any(|known| known == name || known == stadium)
+        })
+    }
+
+    /// The team of ours playing in `game`: the home team when it is one of ours, the away
+    /// team otherwise.
+    pub fn our_team(&self, game: &Game) -> Option<&Team> {
+        let details = game.details();
+        details
+            .home_team
+            .iter()
+            .chain(&details.away_team)
+            .find_map(|name| self.team(name))
+    }
+
+    /// Where `game` is played: the venue the schedule gives when it is one of ours, and
+    /// otherwise the home venue of the home team when that is one of ours.
+    pub fn venue_of(&self, game: &Game) -> Option<&Venue> {
+        let details = game.details();
+        match &details.venue {
+            Some(venue) => self.venue(venue),
+            None => {
+                let home_team = self.team(details.home_team.as_ref()?)?;
+                self.venue(&home_team.venue)
+            }
+        }
+    }
+
+    /// Calls our teams and the venue of `game` by the registry's names for them, filling the
+    /// venue in from the home team's when the schedule leaves it out, and counts every game
+    /// outside baseball as regular season, since `GameType`s are worked out from the baseball
+    /// calendar.
+    pub fn normalise(&self, game: &mut Game) {
+        let venue = self.venue_of(game).map(|venue| venue.name.clone());
+        let sport = self.our_team(game).map(|team| team.sport);
+
+        let details = game.details_mut();
+        for name in details
+            .home_team
+            .iter_mut()
+            .chain(details.away_team.iter_mut())
+        {
+            if let Some(team) = self.team(name) {
+                *name = team.name.clone();
+            }
+        }
+        if venue.is_some() {
+            details.venue = venue;
+        }
+        if sport.is_some() && sport != Some(Sport::Baseball) {
+            details.game_type = GameType::RegularSeason;
+        }
+    }
+}
+
+/// Whether two names are for the same team, allowing for one saying "Red Sox" where the
+/// other says "Boston Red Sox".
+pub(crate) fn same_team_name(a: &str, b: &str) -> bool {
+    let (a, b) = (a.trim().to_lowercase(), b.trim().to_lowercase());
+    a == b || a.ends_with(&format!(" {}", b)) || b.ends_with(&format!(" {}", a))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ics::parse_games_from_ics;
+
+    #[test]
+    fn it_knows_whose_game_it_is_and_where() {
+        let registry = Registry::sodo();
+        let mut games = parse_games_from_ics(
+            "BEGIN:VCALENDAR\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART:20191020T201000Z\r\n\
+SUMMARY:Seattle Seahawks vs. Baltimore Ravens\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART:20190907T020000Z\r\n\
+SUMMARY:Sounders FC vs LA Galaxy\r\n\
+LOCATION:CenturyLink Field, Seattle\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART:20190329T021000Z\r\n\
+SUMMARY:Red Sox at Mariners\r\n\
+LOCATION:T-Mobile Park - Seattle\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n",
+        )
+        .into_iter()
+        .map(Result::unwrap)
+        .collect::<Vec<Game>>();
+        for game in games.iter_mut() {
+            registry.normalise(game);
+        }
+
+        assert_eq!(
+            vec![
+                (Some("Seahawks"), Some("Lumen Field")),
+                (Some("Sounders"), Some("Lumen Field")),
+                (Some("Mariners"), Some("T-Mobile Park")),
+            ],
+            games
+                .iter()
+                .map(|game| (
+                    registry.our_team(game).map(|team| team.name.as_str()),
+                    registry.venue_of(game).map(|venue| venue.name.as_str())
+                ))
+                .collect::<Vec<(Option<&str>, Option<&str>)>>()
+        );
+        assert_eq!(
+            Some("Baltimore Ravens".to_string()),
+            games[0].details().away_team
+        );
+        assert_eq!(Some("Seahawks".to_string()), games[0].details().home_team);
+        assert_eq!(GameType::RegularSeason, games[0].details().game_type);
+        assert_eq!(Some("Lumen Field".to_string()), games[1].details().venue);
+        assert_eq!(None, registry.team("White Sox"));
+    }
+}
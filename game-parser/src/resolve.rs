@@ -0,0 +1,284 @@
+//! Turns the games `parse_game_from` could only partly read into ones with a start time,
+//! and reports the ones it can't so the schedule can be fixed by hand.
+
+use std::fmt;
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use csv_reader::Record;
+
+use crate::columns::{END_DATE, END_DATE_ET, REMINDER_DATE, START_TIME};
+use crate::dates::DateFormats;
+use crate::status::game_day;
+use crate::{from_pacific, parse_date, parse_time, Game, GameDetails, GameStatus};
+
+/// Games are not expected to run longer than this, which is how a time is matched up with
+/// a date from another column.
+const LONGEST_GAME_HOURS: i64 = 8;
+
+#[derive(Debug, Clone, Default)]
+pub struct ResolveOptions {
+    /// Start time to assume for games that only have a day. Without one they are left as
+    /// they are, so the daemon can warn about them all day instead.
+    pub default_first_pitch: Option<NaiveTime>,
+    /// How the schedule writes its dates.
+    pub date_formats: DateFormats,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UnresolvedReason {
+    NoStartDay,
+    EasternTimeMismatch,
+    NoStart,
+}
+
+impl fmt::Display for UnresolvedReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnresolvedReason::NoStartDay => {
+                write!(f, "it has a start time but no other column gives its day")
+            }
+            UnresolvedReason::EasternTimeMismatch => {
+                write!(f, "its start time and START TIME ET disagree")
+            }
+            UnresolvedReason::NoStart => write!(f, "it has neither a start day nor a time"),
+        }
+    }
+}
+
+/// A game that could not be given a start time, and where it came from when that is
+/// known.
+#[derive(Debug, PartialEq)]
+pub struct Unresolved {
+    pub line: Option<usize>,
+    pub game: Box<Game>,
+    pub reason: UnresolvedReason,
+}
+
+impl fmt::Display for Unresolved {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        let details = self.game.details();
+        match (&details.away_team, &details.home_team) {
+            (Some(away), Some(home)) => write!(f, "{} at {}", away, home)?,
+            _ => write!(f, "game")?,
+        }
+        write!(f, " is unresolved: {}", self.reason)
+    }
+}
+
+/// Gives `game` a start time where the schedule allows:
+///
+/// - day-only games start at `options.default_first_pitch`, if there is one;
+/// - time-only games take their day from END DATE, END DATE ET or REMINDER DATE of
+///   `record`, whichever puts the start within a few hours before the end;
+/// - games with neither take both from the above.
+///
+/// Games that already have a start, or that are not going ahead, come back unchanged.
+pub fn resolve_game(
+    game: Game,
+    record: Option<&Record>,
+    options: &ResolveOptions,
+) -> Result<Game, Unresolved> {
+    let unresolved = |game: Game, reason: UnresolvedReason| Unresolved {
+        line: record.map(Record::line),
+        game: Box::new(game),
+        reason,
+    };
+
+    match game {
+        Game::PerfectlyScheduledGame { .. } => Ok(game),
+        _ if is_called_off(game.details()) => Ok(game),
+        Game::GameWithDay { ref details, .. } => {
+            let start_date_time = match (game_day(&game), options.default_first_pitch) {
+                (Some(day), Some(first_pitch)) => from_pacific(day.and_time(first_pitch)),
+                _ => None,
+            };
+            match start_date_time {
+                Some(start_date_time) => Ok(Game::PerfectlyScheduledGame {
+                    start_date_time,
+                    details: GameDetails {
+                        status: GameStatus::Scheduled,
+                        ..details.clone()
+                    },
+                }),
+                None => Ok(game),
+            }
+        }
+        Game::GameWithTime {
+            start_time,
+            details,
+        } => {
+            let time = record.and_then(|record| parse_time(record, START_TIME).ok());
+            match time.and_then(|time| fuse(time, record?, &details, &options.date_formats)) {
+                Some(start_date_time) => Ok(Game::PerfectlyScheduledGame {
+                    start_date_time,
+                    details,
+                }),
+                None => Err(unresolved(
+                    Game::GameWithTime {
+                        start_time,
+                        details,
+                    },
+                    UnresolvedReason::NoStartDay,
+                )),
+            }
+        }
+        Game::UnclearGame {
+            start_date_time: Some(_),
+            ..
+        } => Err(unresolved(game, UnresolvedReason::EasternTimeMismatch)),
+        Game::UnclearGame { ref details, .. } => {
+            let start = match (record, options.default_first_pitch) {
+                (Some(record), Some(first_pitch)) => {
+                    fuse(first_pitch, record, details, &options.date_formats)
+                }
+                _ => None,
+            };
+            match start {
+                Some(start_date_time) => Ok(Game::PerfectlyScheduledGame {
+                    start_date_time,
+                    details: details.clone(),
+                }),
+                None => Err(unresolved(game, UnresolvedReason::NoStart)),
+            }
+        }
+    }
+}
+
+/// Resolves every game, keeping the order of those that could be resolved.
+pub fn resolve_games<'a, I>(games: I, options: &ResolveOptions) -> (Vec<Game>, Vec<Unresolved>)
+where
+    I: IntoIterator<Item = (Game, Option<&'a Record>)>,
+{
+    let mut resolved = vec![];
+    let mut unresolved = vec![];
+    for (game, record) in games {
+        match resolve_game(game, record, options) {
+            Ok(game) => resolved.push(game),
+            Err(game) => unresolved.push(game),
+        }
+    }
+    (resolved, unresolved)
+}
+
+fn is_called_off(details: &GameDetails) -> bool {
+    match details.status {
+        GameStatus::Postponed | GameStatus::Suspended | GameStatus::Cancelled => true,
+        GameStatus::Scheduled | GameStatus::TimeTBD => false,
+    }
+}
+
+fn fuse(
+    time: NaiveTime,
+    record: &Record,
+    details: &GameDetails,
+    date_formats: &DateFormats,
+) -> Option<DateTime<Utc>> {
+    let days = [END_DATE, END_DATE_ET, REMINDER_DATE]
+        .iter()
+        .filter_map(|column| parse_date(record, column, date_formats).ok())
+        .flat_map(|day| vec![day, day - Duration::days(1)])
+        .collect::<Vec<NaiveDate>>();
+
+    match details.end_date_time {
+        Some(end) => days
+            .into_iter()
+            .filter_map(|day| from_pacific(day.and_time(time)))
+            .find(|start| *start <= end && end - *start <= Duration::hours(LONGEST_GAME_HOURS)),
+        None => days
+            .into_iter()
+            .next()
+            .and_then(|day| from_pacific(day.and_time(time))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_game_from;
+    use chrono::TimeZone;
+
+    const SCHEDULE: &str = "START DATE,START TIME,START TIME ET,SUBJECT,END DATE,END TIME\r\n\
+07/09/19,,,NL All-Stars at AL All-Stars - Time TBD,07/09/19,\r\n\
+,11:10 PM,02:10 AM,Angels at Mariners,07/21/19,01:40 AM\r\n\
+,,,Astros at Mariners,,\r\n\
+05/20/19,9:31 PM,03:10 PM,Athletics at Mariners,02/22/19,03:10 PM\r\n";
+
+    fn resolve(options: &ResolveOptions) -> Vec<Result<Game, Unresolved>> {
+        let records = csv_reader::read_records(SCHEDULE);
+        records
+            .iter()
+            .map(|record| resolve_game(parse_game_from(record).unwrap(), Some(record), options))
+            .collect()
+    }
+
+    fn start(result: &Result<Game, Unresolved>) -> Option<DateTime<Utc>> {
+        match result {
+            Ok(Game::PerfectlyScheduledGame {
+                start_date_time, ..
+            }) => Some(*start_date_time),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn it_fills_in_start_times_where_it_can() {
+        let results = resolve(&ResolveOptions {
+            default_first_pitch: NaiveTime::from_hms_opt(19, 10, 0),
+            ..ResolveOptions::default()
+        });
+
+        assert_eq!(
+            Some(Utc.ymd(2019, 7, 10).and_hms(2, 10, 0)),
+            start(&results[0])
+        );
+        assert_eq!(
+            Some(GameStatus::Scheduled),
+            results[0].as_ref().ok().map(|game| game.details().status)
+        );
+        assert_eq!(
+            Some(Utc.ymd(2019, 7, 21).and_hms(6, 10, 0)),
+            start(&results[1])
+        );
+        assert_eq!(
+            vec![
+                "line 4: Astros at Mariners is unresolved: it has neither a start day nor a time",
+                "line 5: Athletics at Mariners is unresolved: its start time and START TIME ET disagree",
+            ],
+            results[2..]
+                .iter()
+                .map(|result| result.as_ref().unwrap_err().to_string())
+                .collect::<Vec<String>>()
+        );
+    }
+
+    #[test]
+    fn it_leaves_day_only_games_alone_without_a_default_first_pitch() {
+        let records = csv_reader::read_records(SCHEDULE);
+        let games = records
+            .iter()
+            .map(|record| (parse_game_from(record).unwrap(), Some(record)));
+        let (resolved, unresolved) = resolve_games(games, &ResolveOptions::default());
+
+        match &resolved[0] {
+            Game::GameWithDay { start_day, details } => {
+                assert_eq!("07/09/19", start_day);
+                assert_eq!(GameStatus::TimeTBD, details.status);
+            }
+            other => panic!("{:?}", other),
+        }
+        assert_eq!(2, resolved.len());
+        assert_eq!(
+            vec![
+                UnresolvedReason::NoStart,
+                UnresolvedReason::EasternTimeMismatch
+            ],
+            unresolved
+                .into_iter()
+                .map(|unresolved| unresolved.reason)
+                .collect::<Vec<UnresolvedReason>>()
+        );
+    }
+}
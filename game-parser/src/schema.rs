@@ -0,0 +1,330 @@
+//! Checks a schedule still looks like the MLB calendar export before anything is
+//! scheduled from it.
+
+use std::fmt;
+
+use chrono::{NaiveDate, NaiveTime};
+use csv_reader::Record;
+use serde::Serialize;
+
+use crate::columns::*;
+use crate::dates::DateFormats;
+
+const DATE_COLUMNS: [&str; 4] = [START_DATE, END_DATE, END_DATE_ET, REMINDER_DATE];
+const TIME_COLUMNS: [&str; 6] = [
+    START_TIME,
+    START_TIME_ET,
+    END_TIME,
+    END_TIME_ET,
+    REMINDER_TIME,
+    REMINDER_TIME_ET,
+];
+
+#[derive(Debug, PartialEq, Serialize)]
+pub enum SchemaIssue {
+    MissingColumn {
+        column: String,
+    },
+    UnexpectedColumn {
+        column: String,
+    },
+    RenamedColumn {
+        expected: String,
+        found: String,
+    },
+    BadDate {
+        line: usize,
+        column: String,
+        value: String,
+    },
+    BadTime {
+        line: usize,
+        column: String,
+        value: String,
+    },
+    EndBeforeStart {
+        line: usize,
+        start: String,
+        end: String,
+    },
+    EasternTimeMismatch {
+        line: usize,
+        start_time: String,
+        start_time_et: String,
+    },
+}
+
+impl fmt::Display for SchemaIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaIssue::MissingColumn { column } => write!(f, "column {:?} is missing", column),
+            SchemaIssue::UnexpectedColumn { column } => {
+                write!(f, "column {:?} is not part of the MLB export", column)
+            }
+            SchemaIssue::RenamedColumn { expected, found } => write!(
+                f,
+                "column {:?} looks like it was renamed to {:?}",
+                expected, found
+            ),
+            SchemaIssue::BadDate {
+                line,
+                column,
+                value,
+            } => write!(f, "line {}: {} {:?} is not a date", line, column, value),
+            SchemaIssue::BadTime {
+                line,
+                column,
+                value,
+            } => write!(
+                f,
+                "line {}: {} {:?} is not a HH:MM AM/PM time",
+                line, column, value
+            ),
+            SchemaIssue::EndBeforeStart { line, start, end } => write!(
+                f,
+                "line {}: game ends ({}) before it starts ({})",
+                line, end, start
+            ),
+            SchemaIssue::EasternTimeMismatch {
+                line,
+                start_time,
+                start_time_et,
+            } => write!(
+                f,
+                "line {}: {} {:?} is not the same time as {} {:?}",
+                line, START_TIME, start_time, START_TIME_ET, start_time_et
+            ),
+        }
+    }
+}
+
+/// Compares `headers` against `MLB_HEADERS`, when they are the MLB export's, and checks
+/// every date and time column of every record parses. Time columns may be blank or `TBD`
+/// for games without a time yet. A column that is missing where an unknown one now sits
+/// is reported as renamed, and a START TIME ET that is not the same moment as the Seattle
+/// start time is reported too.
+pub fn validate_schedule(
+    headers: &[String],
+    records: &[Record],
+    date_formats: &DateFormats,
+) -> Vec<SchemaIssue> {
+    let mut issues = if is_mlb_export(headers) {
+        header_issues(headers)
+    } else {
+        vec![]
+    };
+
+    for record in records {
+        for column in DATE_COLUMNS.iter() {
+            if let Some(value) = record.get(column) {
+                if date_formats.parse(value).is_err() {
+                    issues.push(SchemaIssue::BadDate {
+                        line: record.line(),
+                        column: column.to_string(),
+                        value: value.to_string(),
+                    });
+                }
+            }
+        }
+
+        for column in TIME_COLUMNS.iter() {
+            if let Some(value) = record.get(column) {
+                if !is_time_to_be_decided(value) && parse_time(value).is_none() {
+                    issues.push(SchemaIssue::BadTime {
+                        line: record.line(),
+                        column: column.to_string(),
+                        value: value.to_string(),
+                    });
+                }
+            }
+        }
+
+        let start = day_and_time(record, START_DATE, START_TIME, date_formats);
+        let end = day_and_time(record, END_DATE, END_TIME, date_formats);
+        if let (Some(start), Some(end)) = (start, end) {
+            let ends_first = match (start, end) {
+                ((start_day, Some(start_time)), (end_day, Some(end_time))) => {
+                    end_day.and_time(end_time) < start_day.and_time(start_time)
+                }
+                ((start_day, _), (end_day, _)) => end_day < start_day,
+            };
+            if ends_first {
+                issues.push(SchemaIssue::EndBeforeStart {
+                    line: record.line(),
+                    start: describe_moment(start),
+                    end: describe_moment(end),
+                });
+            }
+        }
+
+        if let (Some(time), Some(time_et)) = (record.get(START_TIME), record.get(START_TIME_ET)) {
+            match crate::parse_date_time(record, START_DATE, START_TIME, date_formats) {
+                Ok(start) if !crate::agrees_with_eastern_time(record, start) => {
+                    issues.push(SchemaIssue::EasternTimeMismatch {
+                        line: record.line(),
+                        start_time: time.to_string(),
+                        start_time_et: time_et.to_string(),
+                    })
+                }
+                _ => {}
+            }
+        }
+    }
+
+    issues
+}
+
+/// Whether `headers` are the MLB export's, give or take a few having drifted. A CSV with
+/// only some of its columns, such as a file of manual overrides, isn't held to the rest.
+fn is_mlb_export(headers: &[String]) -> bool {
+    let known = headers
+        .iter()
+        .filter(|header| MLB_HEADERS.contains(&header.as_str()))
+        .count();
+    known * 2 > MLB_HEADERS.len()
+}
+
+fn header_issues(headers: &[String]) -> Vec<SchemaIssue> {
+    let is_expected = |header: &str| MLB_HEADERS.contains(&header);
+    let mut renamed = vec![];
+    let mut issues = vec![];
+
+    for (index, expected) in MLB_HEADERS.iter().enumerate() {
+        if headers.iter().any(|header| header == expected) {
+            continue;
+        }
+        match headers.get(index) {
+            Some(found) if !is_expected(found) => {
+                renamed.push(found.as_str());
+                issues.push(SchemaIssue::RenamedColumn {
+                    expected: expected.to_string(),
+                    found: found.to_string(),
+                });
+            }
+            _ => issues.push(SchemaIssue::MissingColumn {
+                column: expected.to_string(),
+            }),
+        }
+    }
+
+    for header in headers {
+        if !is_expected(header) && !renamed.contains(&header.as_str()) {
+            issues.push(SchemaIssue::UnexpectedColumn {
+                column: header.to_string(),
+            });
+        }
+    }
+
+    issues
+}
+
+fn is_time_to_be_decided(value: &str) -> bool {
+    value.trim().is_empty() || value.trim().eq_ignore_ascii_case("TBD")
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), TIME_FORMAT).ok()
+}
+
+/// The day in `date_column` and, when it has one, the time in `time_column`, in the
+/// export's own timezone. Without both times only the days are compared.
+fn day_and_time(
+    record: &Record,
+    date_column: &str,
+    time_column: &str,
+    date_formats: &DateFormats,
+) -> Option<(NaiveDate, Option<NaiveTime>)> {
+    let date = date_formats.parse(record.get(date_column)?).ok()?;
+    Some((date, record.get(time_column).and_then(parse_time)))
+}
+
+fn describe_moment((day, time): (NaiveDate, Option<NaiveTime>)) -> String {
+    match time {
+        Some(time) => day.and_time(time).to_string(),
+        None => day.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(contents: &str) -> Vec<SchemaIssue> {
+        let records = csv_reader::read_records(contents);
+        let headers = csv_reader::read_rows(contents).remove(0);
+        validate_schedule(&headers, &records, &DateFormats::default())
+    }
+
+    #[test]
+    fn it_only_flags_the_hand_edited_row_of_the_shipped_schedule() {
+        assert_eq!(
+            vec![
+                SchemaIssue::EndBeforeStart {
+                    line: 2,
+                    start: "2019-05-20 21:31:00".to_string(),
+                    end: "2019-02-22 15:10:00".to_string()
+                },
+                SchemaIssue::EasternTimeMismatch {
+                    line: 2,
+                    start_time: "9:31 PM".to_string(),
+                    start_time_et: "03:10 PM".to_string()
+                }
+            ],
+            validate(include_str!("../../seattle-mariners-home-schedule.csv"))
+        );
+    }
+
+    #[test]
+    fn it_reports_schema_drift() {
+        let headers = MLB_HEADERS
+            .iter()
+            .map(|header| match *header {
+                SUBJECT => "MATCHUP".to_string(),
+                header => header.to_string(),
+            })
+            .filter(|header| header != REMINDER_OFF)
+            .chain(vec!["TICKETS".to_string()])
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            vec![
+                SchemaIssue::RenamedColumn {
+                    expected: SUBJECT.to_string(),
+                    found: "MATCHUP".to_string()
+                },
+                SchemaIssue::MissingColumn {
+                    column: REMINDER_OFF.to_string()
+                },
+                SchemaIssue::UnexpectedColumn {
+                    column: "TICKETS".to_string()
+                },
+            ],
+            validate_schedule(&headers, &[], &DateFormats::default())
+        );
+    }
+
+    #[test]
+    fn it_reports_bad_dates_times_and_games_ending_before_they_start() {
+        let issues = validate(
+            "START DATE,START TIME,END DATE,END TIME\n\
+             28.03.2019,19:10,03/28/19,TBD\n\
+             03/29/19,07:10 PM,03/28/19,10:10 PM\n\
+             07/09/19,07:10 PM,07/09/19,\n\
+             07/10/19,07:10 PM,07/09/19,\n",
+        );
+
+        assert_eq!(
+            vec![
+                "line 2: START DATE \"28.03.2019\" is not a date".to_string(),
+                "line 2: START TIME \"19:10\" is not a HH:MM AM/PM time".to_string(),
+                "line 3: game ends (2019-03-28 22:10:00) before it starts (2019-03-29 19:10:00)"
+                    .to_string(),
+                "line 5: game ends (2019-07-09) before it starts (2019-07-10 19:10:00)".to_string(),
+            ],
+            issues
+                .iter()
+                .map(SchemaIssue::to_string)
+                .collect::<Vec<String>>()
+        );
+    }
+}
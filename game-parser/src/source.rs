@@ -0,0 +1,435 @@
+//! Where schedules come from. Every kind of schedule is an `EventSource`, so a new one,
+//! such as a concert listing, only needs an implementation and a `SourceKind`.
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, Utc};
+use csv_reader::{CsvError, Record};
+use serde::Deserialize;
+
+use crate::ics::parse_games_from_ics;
+use crate::resolve::{resolve_game, ResolveOptions, Unresolved};
+use crate::schema::{validate_schedule, SchemaIssue};
+use crate::stats_api::{
+    fetch_stats_api_schedule, read_stats_api_file, MARINERS_TEAM_ID, STATS_API_BASE_URL,
+};
+use crate::{parse_games_with, Game, ParseError, SourceError};
+
+pub trait EventSource {
+    /// What to call the source in logs and on the dashboard.
+    fn name(&self) -> &str;
+
+    /// Reads the source's games, resolved as far as `resolve::resolve_game` can, along with
+    /// anything wrong with the schedule that didn't stop it being read.
+    fn fetch(&self) -> Result<Events, SourceError>;
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Events {
+    pub games: Vec<Game>,
+    pub problems: Vec<Problem>,
+}
+
+/// Something wrong with part of a schedule. The games it affects are left out of
+/// `Events::games`.
+#[derive(Debug, PartialEq)]
+pub enum Problem {
+    Csv(CsvError),
+    Parse(ParseError),
+    Schema(SchemaIssue),
+    Unresolved(Unresolved),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Csv(error) => write!(f, "{}", error),
+            Problem::Parse(error) => write!(f, "{}", error),
+            Problem::Schema(issue) => write!(f, "{}", issue),
+            Problem::Unresolved(unresolved) => write!(f, "{}", unresolved),
+        }
+    }
+}
+
+/// The MLB CSV export, checked against the export's columns.
+pub struct CsvSource {
+    path: PathBuf,
+    name: String,
+    resolve_options: ResolveOptions,
+}
+
+impl CsvSource {
+    pub fn new<P: AsRef<Path>>(path: P, resolve_options: ResolveOptions) -> CsvSource {
+        CsvSource {
+            path: path.as_ref().to_path_buf(),
+            name: path.as_ref().display().to_string(),
+            resolve_options,
+        }
+    }
+}
+
+impl EventSource for CsvSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn fetch(&self) -> Result<Events, SourceError> {
+        let file = File::open(&self.path).map_err(|error| SourceError::Io {
+            path: self.name.clone(),
+            error: error.to_string(),
+        })?;
+        let mut reader = csv_reader::Reader::sniffed(BufReader::new(file))
+            .to_end()
+            .records();
+        let mut records = vec![];
+        let mut bad_rows = vec![];
+        for record in reader.by_ref() {
+            match record {
+                Ok(record) => records.push(record),
+                Err(error @ CsvError::Io { .. }) => {
+                    return Err(SourceError::Io {
+                        path: self.name.clone(),
+                        error: error.to_string(),
+                    })
+                }
+                Err(error) => bad_rows.push(Problem::Csv(error)),
+            }
+        }
+        let headers = reader.headers().unwrap_or_default();
+
+        let mut events = resolve_events(
+            parse_games_with(&records, &self.resolve_options.date_formats),
+            &records,
+            &self.resolve_options,
+        );
+        let issues = validate_schedule(headers, &records, &self.resolve_options.date_formats);
+        events.problems.splice(
+            0..0,
+            issues.into_iter().map(Problem::Schema).chain(bad_rows),
+        );
+        Ok(events)
+    }
+}
+
+/// An iCalendar feed saved as a file.
+pub struct IcsSource {
+    path: PathBuf,
+    name: String,
+    resolve_options: ResolveOptions,
+}
+
+impl IcsSource {
+    pub fn new<P: AsRef<Path>>(path: P, resolve_options: ResolveOptions) -> IcsSource {
+        IcsSource {
+            path: path.as_ref().to_path_buf(),
+            name: path.as_ref().display().to_string(),
+            resolve_options,
+        }
+    }
+}
+
+impl EventSource for IcsSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn fetch(&self) -> Result<Events, SourceError> {
+        let contents = fs::read_to_string(&self.path).map_err(|error| SourceError::Io {
+            path: self.name.clone(),
+            error: error.to_string(),
+        })?;
+        Ok(resolve_events(
+            parse_games_from_ics(&contents),
+            &[],
+            &self.resolve_options,
+        ))
+    }
+}
+
+enum StatsApiLocation {
+    File(PathBuf),
+    Url {
+        base_url: String,
+        team_id: u32,
+        season: i32,
+    },
+}
+
+/// The Stats API schedule, as JSON saved to a file or fetched from the API.
+pub struct StatsApiSource {
+    location: StatsApiLocation,
+    name: String,
+    resolve_options: ResolveOptions,
+}
+
+impl StatsApiSource {
+    pub fn file<P: AsRef<Path>>(path: P, resolve_options: ResolveOptions) -> StatsApiSource {
+        StatsApiSource {
+            location: StatsApiLocation::File(path.as_ref().to_path_buf()),
+            name: path.as_ref().display().to_string(),
+            resolve_options,
+        }
+    }
+
+    pub fn url(
+        base_url: &str,
+        team_id: u32,
+        season: i32,
+        resolve_options: ResolveOptions,
+    ) -> StatsApiSource {
+        StatsApiSource {
+            location: StatsApiLocation::Url {
+                base_url: base_url.to_string(),
+                team_id,
+                season,
+            },
+            name: "the Stats API".to_string(),
+            resolve_options,
+        }
+    }
+}
+
+impl EventSource for StatsApiSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn fetch(&self) -> Result<Events, SourceError> {
+        let games = match &self.location {
+            StatsApiLocation::File(path) => read_stats_api_file(path),
+            StatsApiLocation::Url {
+                base_url,
+                team_id,
+                season,
+            } => fetch_stats_api_schedule(base_url, *team_id, *season),
+        }
+        .map_err(|error| SourceError::StatsApi { error })?;
+        Ok(resolve_events(
+            games.into_iter().map(Ok).collect(),
+            &[],
+            &self.resolve_options,
+        ))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceKind {
+    Csv,
+    Ics,
+    StatsApi,
+}
+
+/// One entry of the daemon's `sources`. `kind` can be left out for files, and is then
+/// worked out from the extension: `.ics` for iCalendar, `.json` for the Stats API and the
+/// CSV export otherwise. A Stats API source without a `path` is fetched from `base_url`,
+/// for `team_id` and `season`, which default to the API itself, the Mariners and this
+/// year.
+#[derive(Debug, PartialEq, Clone, Default, Deserialize)]
+pub struct SourceConfig {
+    pub kind: Option<SourceKind>,
+    pub name: Option<String>,
+    pub path: Option<String>,
+    pub base_url: Option<String>,
+    pub team_id: Option<u32>,
+    pub season: Option<i32>,
+}
+
+impl SourceConfig {
+    pub fn source(
+        &self,
+        resolve_options: &ResolveOptions,
+    ) -> Result<Box<dyn EventSource>, SourceError> {
+        let options = resolve_options.clone();
+        let kind = match (self.kind, &self.path) {
+            (Some(kind), _) => kind,
+            (None, Some(path)) => kind_of(path),
+            (None, None) => {
+                return Err(SourceError::Config {
+                    reason: "a source needs a kind or a path".to_string(),
+                })
+            }
+        };
+
+        let source: Box<dyn EventSource> = match (kind, &self.path) {
+            (SourceKind::StatsApi, None) => Box::new(StatsApiSource::url(
+                self.base_url.as_deref().unwrap_or(STATS_API_BASE_URL),
+                self.team_id.unwrap_or(MARINERS_TEAM_ID),
+                self.season.unwrap_or_else(|| Utc::now().year()),
+                options,
+            )),
+            (SourceKind::StatsApi, Some(path)) => Box::new(StatsApiSource::file(path, options)),
+            (SourceKind::Csv, Some(path)) => Box::new(CsvSource::new(path, options)),
+            (SourceKind::Ics, Some(path)) => Box::new(IcsSource::new(path, options)),
+            (_, None) => {
+                return Err(SourceError::Config {
+                    reason: "csv and ics sources need a path".to_string(),
+                })
+            }
+        };
+
+        Ok(match &self.name {
+            Some(name) => Box::new(Named {
+                name: name.clone(),
+                source,
+            }),
+            None => source,
+        })
+    }
+}
+
+/// The source for a schedule file, by its extension (see `SourceConfig`).
+pub fn from_path(path: &str, resolve_options: &ResolveOptions) -> Box<dyn EventSource> {
+    let options = resolve_options.clone();
+    match kind_of(path) {
+        SourceKind::Ics => Box::new(IcsSource::new(path, options)),
+        SourceKind::StatsApi => Box::new(StatsApiSource::file(path, options)),
+        SourceKind::Csv => Box::new(CsvSource::new(path, options)),
+    }
+}
+
+fn kind_of(path: &str) -> SourceKind {
+    let path = path.to_lowercase();
+    if path.ends_with(".ics") {
+        SourceKind::Ics
+    } else if path.ends_with(".json") {
+        SourceKind::StatsApi
+    } else {
+        SourceKind::Csv
+    }
+}
+
+struct Named {
+    name: String,
+    source: Box<dyn EventSource>,
+}
+
+impl EventSource for Named {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn fetch(&self) -> Result<Events, SourceError> {
+        self.source.fetch()
+    }
+}
+
+/// Resolves each game, pairing it with the CSV record it came from when there are
+/// `records`, and sets aside those that can't be read or resolved.
+fn resolve_events(
+    games: Vec<Result<Game, ParseError>>,
+    records: &[Record],
+    resolve_options: &ResolveOptions,
+) -> Events {
+    let mut events = Events::default();
+    for (index, game) in games.into_iter().enumerate() {
+        match game.map(|game| resolve_game(game, records.get(index), resolve_options)) {
+            Ok(Ok(game)) => events.games.push(game),
+            Ok(Err(unresolved)) => events.problems.push(Problem::Unresolved(unresolved)),
+            Err(error) => events.problems.push(Problem::Parse(error)),
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(json: &str) -> SourceConfig {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn it_reads_the_shipped_schedule_through_a_source() {
+        let source = config(r#"{ "path": "../seattle-mariners-home-schedule.csv" }"#)
+            .source(&ResolveOptions::default())
+            .unwrap();
+        let events = source.fetch().unwrap();
+
+        assert_eq!("../seattle-mariners-home-schedule.csv", source.name());
+        assert_eq!(97, events.games.len());
+        assert_eq!(
+            vec![
+                "line 2: game ends (2019-02-22 15:10:00) before it starts (2019-05-20 21:31:00)",
+                "line 2: START TIME \"9:31 PM\" is not the same time as START TIME ET \"03:10 PM\"",
+                "line 2: Athletics at Mariners is unresolved: its start time and START TIME ET disagree",
+            ],
+            events
+                .problems
+                .iter()
+                .map(Problem::to_string)
+                .collect::<Vec<String>>()
+        );
+    }
+
+    #[test]
+    fn it_keeps_the_good_rows_of_a_hand_edited_schedule() {
+        let path = std::env::temp_dir().join("mariners-warner-hand-edited.csv");
+        fs::write(
+            &path,
+            "START DATE,START TIME,SUBJECT\n\
+             03/28/19,07:10 PM,Red Sox at Mariners\n\
+             03/29/19,07:10 PM\n\
+             03/30/19,06:10 PM,Red Sox at Mariners\n\
+             03/31/19,01:10 PM,\"Red Sox at Mariners\n",
+        )
+        .unwrap();
+        let events = CsvSource::new(&path, ResolveOptions::default())
+            .fetch()
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(2, events.games.len());
+        assert_eq!(
+            vec![
+                Problem::Csv(CsvError::RaggedRow {
+                    line: 3,
+                    column: 18,
+                    expected: 3,
+                    found: 2
+                }),
+                Problem::Csv(CsvError::UnterminatedQuote {
+                    line: 5,
+                    column: 19
+                }),
+            ],
+            events
+                .problems
+                .into_iter()
+                .filter(|problem| matches!(problem, Problem::Csv(_)))
+                .collect::<Vec<Problem>>()
+        );
+    }
+
+    #[test]
+    fn it_builds_sources_from_config() {
+        let named = config(r#"{ "kind": "stats_api", "name": "MLB", "season": 2019 }"#)
+            .source(&ResolveOptions::default())
+            .unwrap();
+        assert_eq!("MLB", named.name());
+
+        assert_eq!(
+            Some(SourceError::Io {
+                path: "missing.ics".to_string(),
+                error: "No such file or directory (os error 2)".to_string(),
+            }),
+            config(r#"{ "path": "missing.ics" }"#)
+                .source(&ResolveOptions::default())
+                .unwrap()
+                .fetch()
+                .err()
+        );
+        assert_eq!(
+            Some(SourceError::Config {
+                reason: "csv and ics sources need a path".to_string(),
+            }),
+            config(r#"{ "kind": "csv" }"#)
+                .source(&ResolveOptions::default())
+                .err()
+        );
+    }
+}
@@ -0,0 +1,323 @@
+//! Reads the MLB Stats API schedule, e.g.
+//! `https://statsapi.mlb.com/api/v1/schedule?sportId=1&teamId=136&season=2019`, into the
+//! same `Game`s as the CSV export.
+
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+
+use crate::broadcast::Broadcasts;
+use crate::columns::{DATE_FORMAT, PACIFIC};
+use crate::status::status_from_text;
+use crate::{game_type, Game, GameDetails, GameStatus, GameType, StatsApiError};
+
+pub const STATS_API_BASE_URL: &str = "https://statsapi.mlb.com";
+pub const MARINERS_TEAM_ID: u32 = 136;
+
+#[derive(Deserialize)]
+struct Schedule {
+    dates: Vec<ScheduleDate>,
+}
+
+#[derive(Deserialize)]
+struct ScheduleDate {
+    games: Vec<ScheduledGame>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ScheduledGame {
+    game_pk: Option<u64>,
+    game_date: DateTime<Utc>,
+    official_date: Option<NaiveDate>,
+    game_type: Option<String>,
+    #[serde(default)]
+    status: Status,
+    teams: Teams,
+    venue: Option<Named>,
+    description: Option<String>,
+    double_header: Option<String>,
+    game_number: Option<u8>,
+}
+
+#[derive(Deserialize, Default)]
+struct Status {
+    #[serde(rename = "detailedState")]
+    detailed_state: Option<String>,
+    #[serde(rename = "startTimeTBD", default)]
+    start_time_tbd: bool,
+}
+
+#[derive(Deserialize)]
+struct Teams {
+    home: Side,
+    away: Side,
+}
+
+#[derive(Deserialize)]
+struct Side {
+    team: Named,
+}
+
+#[derive(Deserialize)]
+struct Named {
+    name: String,
+}
+
+/// Games whose time is still to be decided (`status.startTimeTBD`) become `GameWithDay`s,
+/// since their `gameDate` is only a placeholder. Postponements and the like come from
+/// `status.detailedState`, and doubleheaders from `doubleHeader` and `gameNumber`.
+pub fn parse_games_from_stats_api(json: &str) -> Result<Vec<Game>, StatsApiError> {
+    let schedule: Schedule = serde_json::from_str(json).map_err(|error| StatsApiError::Json {
+        error: error.to_string(),
+    })?;
+
+    Ok(schedule
+        .dates
+        .into_iter()
+        .flat_map(|date| date.games)
+        .map(to_game)
+        .collect())
+}
+
+pub fn read_stats_api_file<P: AsRef<Path>>(path: P) -> Result<Vec<Game>, StatsApiError> {
+    let json = fs::read_to_string(&path).map_err(|error| StatsApiError::Io {
+        path: path.as_ref().display().to_string(),
+        error: error.to_string(),
+    })?;
+    parse_games_from_stats_api(&json)
+}
+
+/// Fetches a team's regular and postseason schedule for `season`. `base_url` is
+/// `STATS_API_BASE_URL` outside of tests.
+pub fn fetch_stats_api_schedule(
+    base_url: &str,
+    team_id: u32,
+    season: i32,
+) -> Result<Vec<Game>, StatsApiError> {
+    let url = format!(
+        "{}/api/v1/schedule?sportId=1&teamId={}&season={}",
+        base_url.trim_end_matches('/'),
+        team_id,
+        season
+    );
+    let http_error = |error: reqwest::Error| StatsApiError::Http {
+        url: url.clone(),
+        error: error.to_string(),
+    };
+
+    let json = reqwest::get(&url)
+        .and_then(|response| response.error_for_status())
+        .and_then(|mut response| response.text())
+        .map_err(http_error)?;
+    parse_games_from_stats_api(&json)
+}
+
+fn to_game(game: ScheduledGame) -> Game {
+    let start_day = game
+        .official_date
+        .unwrap_or_else(|| game.game_date.with_timezone(&PACIFIC).naive_local().date());
+    let subject = format!(
+        "{} at {}",
+        game.teams.away.team.name, game.teams.home.team.name
+    );
+
+    let mut details = GameDetails {
+        home_team: Some(game.teams.home.team.name),
+        away_team: Some(game.teams.away.team.name),
+        venue: game.venue.map(|venue| venue.name),
+        end_date_time: None,
+        description: game.description,
+        game_type: GameType::default(),
+        status: status_from_text(game.status.detailed_state.as_deref()).unwrap_or(
+            if game.status.start_time_tbd {
+                GameStatus::TimeTBD
+            } else {
+                GameStatus::Scheduled
+            },
+        ),
+        doubleheader: match game.double_header.as_deref() {
+            Some("Y") | Some("S") => game.game_number,
+            _ => None,
+        },
+        source_id: game.game_pk.map(|game_pk| game_pk.to_string()),
+        broadcasts: Broadcasts::default(),
+    };
+    details.game_type = match game.game_type.as_deref() {
+        Some("S") | Some("E") => GameType::SpringTraining,
+        Some("F") | Some("D") | Some("L") | Some("W") => GameType::Postseason,
+        Some("R") => GameType::RegularSeason,
+        _ => game_type::classify(Some(start_day), Some(&subject), &details),
+    };
+
+    if game.status.start_time_tbd {
+        Game::GameWithDay {
+            start_day: start_day.format(DATE_FORMAT).to_string(),
+            details,
+        }
+    } else {
+        Game::PerfectlyScheduledGame {
+            start_date_time: game.game_date,
+            details,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    const SCHEDULE: &str = r#"{
+  "totalGames": 3,
+  "dates": [
+    {
+      "date": "2019-03-28",
+      "games": [
+        {
+          "gamePk": 566083,
+          "gameType": "R",
+          "gameDate": "2019-03-29T02:10:00Z",
+          "officialDate": "2019-03-28",
+          "status": { "detailedState": "Scheduled", "startTimeTBD": false },
+          "teams": {
+            "away": { "team": { "id": 111, "name": "Boston Red Sox" } },
+            "home": { "team": { "id": 136, "name": "Seattle Mariners" } }
+          },
+          "venue": { "id": 680, "name": "T-Mobile Park" }
+        }
+      ]
+    },
+    {
+      "date": "2019-07-09",
+      "games": [
+        {
+          "gamePk": 599932,
+          "gameType": "A",
+          "gameDate": "2019-07-09T07:33:00Z",
+          "officialDate": "2019-07-09",
+          "status": { "detailedState": "Scheduled", "startTimeTBD": true },
+          "teams": {
+            "away": { "team": { "id": 160, "name": "National League All-Stars" } },
+            "home": { "team": { "id": 159, "name": "American League All-Stars" } }
+          },
+          "venue": { "id": 5, "name": "Progressive Field" }
+        }
+      ]
+    },
+    {
+      "date": "2019-10-04",
+      "games": [
+        {
+          "gamePk": 599335,
+          "gameType": "D",
+          "gameDate": "2019-10-05T00:07:00Z",
+          "status": { "detailedState": "Postponed: Rain", "startTimeTBD": false },
+          "doubleHeader": "S",
+          "gameNumber": 2,
+          "teams": {
+            "away": { "team": { "id": 117, "name": "Houston Astros" } },
+            "home": { "team": { "id": 136, "name": "Seattle Mariners" } }
+          },
+          "venue": { "id": 680, "name": "T-Mobile Park" },
+          "description": "ALDS Game 1"
+        }
+      ]
+    }
+  ]
+}"#;
+
+    #[test]
+    fn it_maps_the_schedule_onto_games() {
+        let games = parse_games_from_stats_api(SCHEDULE).unwrap();
+
+        assert_eq!(
+            vec![
+                Game::PerfectlyScheduledGame {
+                    start_date_time: Utc.ymd(2019, 3, 29).and_hms(2, 10, 0),
+                    details: GameDetails {
+                        home_team: Some("Seattle Mariners".to_string()),
+                        away_team: Some("Boston Red Sox".to_string()),
+                        venue: Some("T-Mobile Park".to_string()),
+                        end_date_time: None,
+                        description: None,
+                        game_type: GameType::RegularSeason,
+                        status: GameStatus::Scheduled,
+                        doubleheader: None,
+                        source_id: Some("566083".to_string()),
+                        broadcasts: Broadcasts::default(),
+                    },
+                },
+                Game::GameWithDay {
+                    start_day: "07/09/19".to_string(),
+                    details: GameDetails {
+                        home_team: Some("American League All-Stars".to_string()),
+                        away_team: Some("National League All-Stars".to_string()),
+                        venue: Some("Progressive Field".to_string()),
+                        end_date_time: None,
+                        description: None,
+                        game_type: GameType::RegularSeason,
+                        status: GameStatus::TimeTBD,
+                        doubleheader: None,
+                        source_id: Some("599932".to_string()),
+                        broadcasts: Broadcasts::default(),
+                    },
+                },
+                Game::PerfectlyScheduledGame {
+                    start_date_time: Utc.ymd(2019, 10, 5).and_hms(0, 7, 0),
+                    details: GameDetails {
+                        home_team: Some("Seattle Mariners".to_string()),
+                        away_team: Some("Houston Astros".to_string()),
+                        venue: Some("T-Mobile Park".to_string()),
+                        end_date_time: None,
+                        description: Some("ALDS Game 1".to_string()),
+                        game_type: GameType::Postseason,
+                        status: GameStatus::Postponed,
+                        doubleheader: Some(2),
+                        source_id: Some("599335".to_string()),
+                        broadcasts: Broadcasts::default(),
+                    },
+                },
+            ],
+            games
+        );
+        assert_eq!(
+            Err(StatsApiError::Json {
+                error: "missing field `dates` at line 1 column 2".to_string()
+            }),
+            parse_games_from_stats_api("{}")
+        );
+    }
+
+    #[test]
+    fn it_fetches_the_schedule_from_a_base_url() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0; 1024];
+            let read = stream.read(&mut request).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                SCHEDULE.len(),
+                SCHEDULE
+            )
+            .unwrap();
+            String::from_utf8_lossy(&request[..read]).into_owned()
+        });
+
+        let games = fetch_stats_api_schedule(&base_url, MARINERS_TEAM_ID, 2019).unwrap();
+        assert_eq!(3, games.len());
+        assert!(server
+            .join()
+            .unwrap()
+            .starts_with("GET /api/v1/schedule?sportId=1&teamId=136&season=2019 HTTP/1.1\r\n"));
+    }
+}
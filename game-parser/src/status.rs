@@ -0,0 +1,179 @@
+//! Whether a game is going ahead as scheduled, and which game of a doubleheader it is.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::columns::{DATE_FORMAT, PACIFIC};
+use crate::Game;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum GameStatus {
+    #[default]
+    Scheduled,
+    TimeTBD,
+    Postponed,
+    Suspended,
+    Cancelled,
+}
+
+impl fmt::Display for GameStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameStatus::Scheduled => write!(f, "scheduled"),
+            GameStatus::TimeTBD => write!(f, "at a time to be decided"),
+            GameStatus::Postponed => write!(f, "postponed"),
+            GameStatus::Suspended => write!(f, "suspended"),
+            GameStatus::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// Reads a status out of free text such as the CSV's SUBJECT and DESCRIPTION, where MLB
+/// appends notes like " - Postponed" or " - Time TBD", or the Stats API's
+/// `status.detailedState`. `None` when the text says nothing either way. A makeup game, or
+/// the resumption of a suspended one, mentions what happened to the game it replaces, so
+/// its text is only read for a TBD time.
+pub fn status_from_text<'a, I: IntoIterator<Item = &'a str>>(texts: I) -> Option<GameStatus> {
+    let texts = texts
+        .into_iter()
+        .map(str::to_lowercase)
+        .collect::<Vec<String>>();
+    let mentions = |word: &str| texts.iter().any(|text| text.contains(word));
+    let replaces_another = ["makeup", "make-up", "rescheduled from", "resumption"]
+        .iter()
+        .any(|phrase| mentions(phrase));
+
+    if replaces_another {
+        if mentions("tbd") {
+            Some(GameStatus::TimeTBD)
+        } else {
+            None
+        }
+    } else if mentions("postponed") {
+        Some(GameStatus::Postponed)
+    } else if mentions("suspended") {
+        Some(GameStatus::Suspended)
+    } else if mentions("cancelled") || mentions("canceled") {
+        Some(GameStatus::Cancelled)
+    } else if mentions("tbd") {
+        Some(GameStatus::TimeTBD)
+    } else {
+        None
+    }
+}
+
+/// Numbers games played by the same teams on the same day 1, 2, ... in start time order,
+/// with games whose time is not known yet last. Games already numbered by their source
+/// are left alone, as are events without both teams, such as concerts, which are not
+/// doubleheaders however many are on one day.
+pub fn mark_doubleheaders<'a, I: IntoIterator<Item = &'a mut Game>>(games: I) {
+    let mut games = games.into_iter().collect::<Vec<&mut Game>>();
+    let mut days: HashMap<(NaiveDate, String, String), Vec<usize>> = HashMap::new();
+    for (index, game) in games.iter().enumerate() {
+        let details = game.details();
+        if let (Some(day), Some(home_team), Some(away_team)) =
+            (game_day(game), &details.home_team, &details.away_team)
+        {
+            days.entry((day, home_team.clone(), away_team.clone()))
+                .or_default()
+                .push(index);
+        }
+    }
+
+    for mut indexes in days.into_values().filter(|indexes| indexes.len() > 1) {
+        indexes.sort_by_key(|index| match &*games[*index] {
+            Game::PerfectlyScheduledGame {
+                start_date_time, ..
+            } => (0, Some(*start_date_time), *index),
+            _ => (1, None, *index),
+        });
+        for (number, index) in indexes.into_iter().enumerate() {
+            let details = games[index].details_mut();
+            if details.doubleheader.is_none() {
+                details.doubleheader = Some(number as u8 + 1);
+            }
+        }
+    }
+}
+
+/// The Seattle calendar day a game is on, when that is known.
+pub fn game_day(game: &Game) -> Option<NaiveDate> {
+    match game {
+        Game::PerfectlyScheduledGame {
+            start_date_time, ..
+        }
+        | Game::UnclearGame {
+            start_date_time: Some(start_date_time),
+            ..
+        } => Some(start_date_time.with_timezone(&PACIFIC).naive_local().date()),
+        Game::GameWithDay { start_day, .. }
+        | Game::UnclearGame {
+            start_day: Some(start_day),
+            ..
+        } => NaiveDate::parse_from_str(start_day.trim(), DATE_FORMAT).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games_from_csv;
+
+    #[test]
+    fn it_reads_status_from_notes() {
+        assert_eq!(
+            Some(GameStatus::Postponed),
+            status_from_text(vec!["Rays at Mariners - Postponed", ""])
+        );
+        assert_eq!(
+            Some(GameStatus::TimeTBD),
+            status_from_text(vec!["NL All-Stars at AL All-Stars - Time TBD"])
+        );
+        assert_eq!(
+            Some(GameStatus::Cancelled),
+            status_from_text(vec!["Canceled: Inclement Weather"])
+        );
+        assert_eq!(None, status_from_text(vec!["Twins at Mariners"]));
+        assert_eq!(
+            None,
+            status_from_text(vec![
+                "Twins at Mariners",
+                "Makeup of April 3 game postponed due to rain"
+            ])
+        );
+        assert_eq!(
+            Some(GameStatus::TimeTBD),
+            status_from_text(vec!["Resumption of suspended game - Time TBD"])
+        );
+    }
+
+    #[test]
+    fn it_numbers_the_games_of_a_doubleheader() {
+        let games = games_from_csv(
+            "START DATE,START TIME,SUBJECT\r\n\
+             07/20/19,,Angels at Mariners - Time TBD\r\n\
+             07/20/19,01:10 PM,Angels at Mariners\r\n\
+             07/21/19,01:10 PM,Angels at Mariners\r\n\
+             07/21/19,07:00 PM,Fireworks Night\r\n\
+             07/21/19,09:00 PM,Fireworks Night\r\n",
+        );
+
+        assert_eq!(
+            vec![
+                (Some(2), GameStatus::TimeTBD),
+                (Some(1), GameStatus::Scheduled),
+                (None, GameStatus::Scheduled),
+                (None, GameStatus::Scheduled),
+                (None, GameStatus::Scheduled)
+            ],
+            games
+                .iter()
+                .map(|game| (game.details().doubleheader, game.details().status))
+                .collect::<Vec<(Option<u8>, GameStatus)>>()
+        );
+    }
+}
@@ -198,7 +198,7 @@ mod tests {
             _: &str,
             _: &str,
         ) -> Box<dyn Future<Item = std::process::Output, Error = std::io::Error> + Send> {
-            let custom_error = std::io::Error::new(std::io::ErrorKind::Other, "oh no!");
+            let custom_error = std::io::Error::other("oh no!");
             Box::new(err(custom_error))
         }
     }
@@ -1,22 +1,18 @@
-use log::{info};
-use chrono::{Duration, FixedOffset, TimeZone, Utc};
-use config::{Config, File as ConfigFile};
+use chrono::{Duration, NaiveTime, TimeZone, Utc};
+use config::{Config, ConfigError, File as ConfigFile};
 use futures::future::join_all;
 use futures::future::Future;
+use log::{info, warn};
 use serde_json::to_string;
 use simplelog::{CombinedLogger, Config as LogConfig, LevelFilter, WriteLogger};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::prelude::*;
-use std::{fs, time as native_time};
+use std::sync::{Arc, Mutex};
+use std::time as native_time;
 use time::Duration as TimeDuration;
 use tokio::timer::Delay;
 
-use csv_reader;
-use dashboard;
-use game_parser;
-use twilio;
-
 fn main() {
     CombinedLogger::init(vec![WriteLogger::new(
         LevelFilter::Info,
@@ -25,77 +21,222 @@ fn main() {
     )])
     .expect("could not initialize logging infrastructure");
 
-    let twilio_config = get_twilio_config();
+    let args = std::env::args().collect::<Vec<String>>();
+    if args.get(1).map(String::as_str) == Some("diff") {
+        return diff_schedules(&args[2..]);
+    }
+
+    let settings = read_settings();
 
-    let filename = "seattle-mariners-home-schedule.csv";
-    let contents = fs::read_to_string(filename)
-        .unwrap_or_else(|_| panic!("Something went wrong reading {}", filename));
+    let twilio_config = get_twilio_config(&settings);
+    let registry = get_registry(&settings);
+    let venue_filter = get_venue_filter(&settings, &registry);
+    let game_type_filter = get_game_type_filter(&settings);
+    let resolve_options = get_resolve_options(&settings);
+    let where_to_watch = settings.get_bool("where_to_watch").unwrap_or(false);
+    let homestand_heads_up = settings.get_bool("homestand_heads_up").unwrap_or(false);
 
-    let real_rows = csv_reader::read_rows(&contents);
+    let sources = get_sources(&settings, &resolve_options);
+    let (schedule, provenance) = load_schedules(&sources, &registry);
 
     let fake_start_date_time = Utc::now() + TimeDuration::seconds(5);
     let fake_game = game_parser::Game::PerfectlyScheduledGame {
         start_date_time: fake_start_date_time,
+        details: game_parser::GameDetails::default(),
+    };
+
+    // Stretches are worked out from the whole schedule, road games included, since only
+    // they show where one homestand ends and the next begins.
+    let heads_ups = if homestand_heads_up {
+        get_homestand_heads_ups(&schedule, &registry, &game_type_filter)
+    } else {
+        vec![]
     };
 
-    let mut perfectly_scheduled_games = parse_perfectly_scheduled_games(real_rows);
+    // Kept to list on the dashboard, including those not sent again below, so that their
+    // statuses are written back out.
+    let heads_up_entries = heads_ups
+        .iter()
+        .filter_map(|alert| Some((alert.game_id.clone()?, alert.purpose.clone())))
+        .collect::<Vec<(game_parser::GameId, String)>>();
+
+    let mut selected_games = select_games(schedule, &venue_filter, &game_type_filter);
 
     let mut games = vec![fake_game];
-    games.append(&mut perfectly_scheduled_games);
-
-    record_parsed_games(&games);
+    games.append(&mut selected_games);
 
-    let jobs: Vec<twilio::TwilioResponseFuture> = games
+    let statuses = read_dashboard_statuses();
+    // A game or heads-up the dashboard already shows as Completed had every text go out in
+    // an earlier run, so reloading the schedule doesn't text about it again, even if it has
+    // moved.
+    let completed = |game_id: Option<&game_parser::GameId>| {
+        game_id.and_then(|id| statuses.get(id)) == Some(&dashboard::WarningStatus::Completed)
+    };
+    let alerts: Vec<GameAlert> = games
         .iter()
-        .filter_map(|game| -> Option<Vec<GameAlert>> { get_times_to_alert(game) })
+        .filter(|game| {
+            let completed = completed(game_parser::id::game_id(game).as_ref());
+            if completed {
+                info!(
+                    "skipping {}: it was already texted about",
+                    describe_game(game)
+                );
+            }
+            !completed
+        })
+        .filter_map(|game| -> Option<Vec<GameAlert>> { get_times_to_alert(game, where_to_watch) })
         .flatten()
-        .map(|time_to_alert: GameAlert| -> twilio::TwilioResponseFuture {
-            create_alert_job(time_to_alert, twilio_config.clone())
+        .chain(heads_ups.into_iter().filter(|alert| {
+            let completed = completed(alert.game_id.as_ref());
+            if completed {
+                info!("skipping the text {:?}: it was already sent", alert.purpose);
+            }
+            !completed
+        }))
+        .filter(|alert| {
+            let ahead = alert.time_to_alert > Utc::now().timestamp_millis();
+            if !ahead {
+                info!("skipping the text {:?}: its time has passed", alert.purpose);
+            }
+            ahead
+        })
+        .collect();
+
+    let dashboard = Arc::new(Mutex::new(Dashboard::new(
+        games,
+        heads_up_entries,
+        statuses,
+        provenance,
+        &alerts,
+    )));
+    dashboard.lock().unwrap().write();
+
+    let jobs: Vec<_> = alerts
+        .into_iter()
+        .map(|time_to_alert: GameAlert| {
+            let game_id = time_to_alert.game_id.clone();
+            let dashboard = Arc::clone(&dashboard);
+            create_alert_job(time_to_alert, twilio_config.clone()).then(move |result| {
+                if let Some(game_id) = &game_id {
+                    dashboard.lock().unwrap().record(game_id, result.is_ok());
+                }
+                Ok::<_, ()>((game_id, result))
+            })
         })
         .collect();
 
-    let all_jobs = join_all(jobs)
+    let all_jobs = join_all(jobs).map(|results| {
+        info!("results {:?}", results);
+    });
+
+    tokio::run(all_jobs)
+}
+
+/// Seattle time of day the all-day warning for a game with a TBD start goes out.
+const ALL_DAY_WARNING_HOUR: u32 = 8;
+
+/// `warner-daemon diff OLD NEW [--json] [--text]` prints what changed between two
+/// schedule files, as JSON with `--json`, and with `--text` also texts the changes. Only
+/// `--text` needs a config.toml; without one the files are read with the default date
+/// formats.
+fn diff_schedules(args: &[String]) {
+    let (flags, files): (Vec<&String>, Vec<&String>) =
+        args.iter().partition(|arg| arg.starts_with("--"));
+    let has_flag = |flag: &str| flags.iter().any(|arg| *arg == flag);
+    if files.len() != 2 {
+        eprintln!("usage: warner-daemon diff OLD NEW [--json] [--text]");
+        std::process::exit(2);
+    }
+
+    let resolve_options = get_resolve_options(&read_optional_settings());
+    let load = |filename: &str| {
+        fetch_games(game_parser::source::from_path(filename, &resolve_options).as_ref())
+            .unwrap_or_else(|error| {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            })
+    };
+    let changes = game_parser::diff::diff_schedules(&load(files[0]), &load(files[1]));
+
+    let lines = changes
+        .iter()
+        .map(|change| change.to_string())
+        .collect::<Vec<String>>();
+    if has_flag("--json") {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&changes).expect("Could not seralize into string")
+        );
+    } else if lines.is_empty() {
+        println!("no changes");
+    } else {
+        println!("{}", lines.join("\n"));
+    }
+
+    if has_flag("--text") && !lines.is_empty() {
+        let t = get_twilio_config(&read_settings());
+        let executor = twilio::http_executor::HTTPExecutor;
+        let text = twilio::send_text_message(
+            &t.from,
+            &t.to,
+            &t.twilio_account_id,
+            &t.twilio_access_token,
+            &format!("the schedule changed:\n{}", lines.join("\n")),
+            &executor,
+        )
         .map(|r| {
             info!("results {:?}", r);
         })
         .map_err(|e| {
             info!("error {:?}", e);
         });
-
-    tokio::run(all_jobs)
+        tokio::run(text)
+    }
 }
 
+/// Where the dashboard is written, one entry per game with the status of its texts.
+const DASHBOARD_FILE: &str = "foo.txt";
+
 struct GameAlert {
+    game_id: Option<game_parser::GameId>,
     purpose: String,
     time_to_alert: i64,
 }
 
-fn get_times_to_alert(game: &game_parser::Game) -> Option<Vec<GameAlert>> {
+/// With `where_to_watch`, games with a local broadcast also get a text at first pitch
+/// saying where to watch or listen.
+fn get_times_to_alert(game: &game_parser::Game, where_to_watch: bool) -> Option<Vec<GameAlert>> {
+    let game_id = game_parser::id::game_id(game);
     match game {
-        game_parser::Game::PerfectlyScheduledGame { start_date_time } => {
+        game_parser::Game::PerfectlyScheduledGame {
+            start_date_time,
+            details,
+        } if details.status == game_parser::GameStatus::Scheduled => {
             let time_betwen_now_and_game: i64 =
                 start_date_time.timestamp_millis() - Utc::now().timestamp_millis();
             if time_betwen_now_and_game <= 0 {
                 None
             } else {
                 let mut game_alerts: Vec<GameAlert> = (1..4)
-                    .map(|minutes: i64| -> Vec<GameAlert> {
+                    .flat_map(|minutes: i64| -> Vec<GameAlert> {
                         let duration = Duration::minutes(minutes).num_milliseconds();
 
                         let n_minutes_before = start_date_time.timestamp_millis() - duration;
                         let n_minutes_after = start_date_time.timestamp_millis() + duration;
 
                         let before_game_purpose =
-                            format!("a mariners game is starting in {} minutes", minutes);
+                            format!("{} is starting in {} minutes", describe_game(game), minutes);
                         let after_game_purpose =
-                            format!("a mariners game started {} minutes ago", minutes);
+                            format!("{} started {} minutes ago", describe_game(game), minutes);
 
                         let mut times_to_go = vec![GameAlert {
+                            game_id: game_id.clone(),
                             time_to_alert: n_minutes_after,
                             purpose: after_game_purpose,
                         }];
 
                         let before_game_alert = GameAlert {
+                            game_id: game_id.clone(),
                             time_to_alert: n_minutes_before,
                             purpose: before_game_purpose,
                         };
@@ -109,40 +250,168 @@ fn get_times_to_alert(game: &game_parser::Game) -> Option<Vec<GameAlert>> {
 
                         times_to_go.into_iter().collect()
                     })
-                    .flatten()
                     .collect();
                 game_alerts.push(GameAlert {
+                    game_id: game_id.clone(),
                     time_to_alert: start_date_time.timestamp_millis(),
-                    purpose: "a mariners game is starting now".to_string(),
+                    purpose: match details.end_date_time {
+                        Some(end_date_time) => format!(
+                            "{} is starting now and should be over by {}",
+                            describe_game(game),
+                            end_date_time
+                                .with_timezone(&game_parser::columns::PACIFIC)
+                                .format("%-I:%M %p")
+                        ),
+                        None => format!("{} is starting now", describe_game(game)),
+                    },
                 });
+                if where_to_watch && !details.broadcasts.local().is_empty() {
+                    game_alerts.push(GameAlert {
+                        game_id,
+                        time_to_alert: start_date_time.timestamp_millis(),
+                        purpose: format!(
+                            "first pitch on {}: {}",
+                            details.broadcasts,
+                            describe_game(game)
+                        ),
+                    });
+                }
                 for game_alert in &game_alerts {
                     info!(
                         "going to text at {:?} for game on {:?} at {:?}",
                         Utc.timestamp_millis(game_alert.time_to_alert)
-                            .with_timezone(&FixedOffset::west(7 * 3600))
+                            .with_timezone(&game_parser::columns::PACIFIC)
                             .to_rfc2822(),
                         start_date_time
-                            .with_timezone(&FixedOffset::west(7 * 3600))
+                            .with_timezone(&game_parser::columns::PACIFIC)
                             .date(),
                         start_date_time
-                            .with_timezone(&FixedOffset::west(7 * 3600))
+                            .with_timezone(&game_parser::columns::PACIFIC)
                             .time()
                     );
                 }
                 Some(game_alerts)
             }
         }
+        game if game.details().status == game_parser::GameStatus::TimeTBD => {
+            get_all_day_warning(game)
+        }
         _ => None,
     }
 }
 
+/// A game without a start time yet could fill SODO at any point, so instead of counting
+/// down to first pitch it gets a single text on the morning of the game.
+fn get_all_day_warning(game: &game_parser::Game) -> Option<Vec<GameAlert>> {
+    let day = game_parser::status::game_day(game)?;
+    let morning = game_parser::from_pacific(day.and_hms(ALL_DAY_WARNING_HOUR, 0, 0))?;
+    if morning <= Utc::now() {
+        return None;
+    }
+
+    info!(
+        "going to text at {:?} for a game on {:?} with no start time yet",
+        morning
+            .with_timezone(&game_parser::columns::PACIFIC)
+            .to_rfc2822(),
+        day
+    );
+    Some(vec![GameAlert {
+        game_id: game_parser::id::game_id(game),
+        time_to_alert: morning.timestamp_millis(),
+        purpose: format!(
+            "{} is today but its start time is TBD, expect SODO to be busy all day",
+            describe_game(game)
+        ),
+    }])
+}
+
+/// One text on the morning each of our baseball teams starts a homestand, since a week of
+/// home games is worth planning around as a whole. Other sports play once a week at most,
+/// so each of their games already gets its own texts. Only games of the types being
+/// texted about count, so spring training doesn't make a homestand. Each heads-up is named
+/// by `id::stretch_id`, so it keeps its status on the dashboard across reloads.
+fn get_homestand_heads_ups(
+    schedule: &[game_parser::Game],
+    registry: &game_parser::registry::Registry,
+    game_type_filter: &game_parser::filter::GameTypeFilter,
+) -> Vec<GameAlert> {
+    let games = schedule
+        .iter()
+        .filter(|game| game_type_filter.check(game).is_ok())
+        .cloned()
+        .collect::<Vec<game_parser::Game>>();
+    registry
+        .teams()
+        .iter()
+        .filter(|team| team.sport == game_parser::registry::Sport::Baseball)
+        .flat_map(|team| {
+            game_parser::homestand::group_stretches(&games, &team.name)
+                .into_iter()
+                .filter(|stretch| stretch.kind == game_parser::homestand::StretchKind::Homestand)
+                .filter_map(move |stretch| {
+                    let morning = game_parser::from_pacific(stretch.start.and_hms(
+                        ALL_DAY_WARNING_HOUR,
+                        0,
+                        0,
+                    ))?;
+                    if morning <= Utc::now() {
+                        return None;
+                    }
+                    info!(
+                        "going to text at {:?} about the {} {}",
+                        morning
+                            .with_timezone(&game_parser::columns::PACIFIC)
+                            .to_rfc2822(),
+                        team.name,
+                        stretch
+                    );
+                    Some(GameAlert {
+                        game_id: game_parser::id::stretch_id(&team.name, &stretch),
+                        time_to_alert: morning.timestamp_millis(),
+                        purpose: format!(
+                            "the {} start a {}, expect SODO to be busy",
+                            team.name, stretch
+                        ),
+                    })
+                })
+        })
+        .collect()
+}
+
+/// Names the matchup and the stadium, e.g. "Twins at Mariners (T-Mobile Park)", calling out
+/// postseason and spring training games. Venues go by the registry's names once the game
+/// has been through `Registry::normalise`.
+fn describe_game(game: &game_parser::Game) -> String {
+    let details = game.details();
+    let matchup = match (&details.away_team, &details.home_team) {
+        (Some(away_team), Some(home_team)) => format!("{} at {}", away_team, home_team),
+        _ => "a game".to_string(),
+    };
+    let matchup = match details.doubleheader {
+        Some(game_number) => format!("{}, game {} of a doubleheader,", matchup, game_number),
+        None => matchup,
+    };
+    let matchup = match &details.venue {
+        Some(venue) => format!("{} ({})", matchup, venue),
+        None => matchup,
+    };
+    match details.game_type {
+        game_parser::GameType::RegularSeason => matchup,
+        game_type => format!("{} game {}", game_type, matchup),
+    }
+}
+
 fn create_alert_job(
     game_alert: GameAlert,
     t: twilio::TwilioConfig,
 ) -> twilio::TwilioResponseFuture {
-    let time_to_sleep = native_time::Duration::from_millis(
-        (game_alert.time_to_alert - Utc::now().timestamp_millis()) as u64,
-    );
+    // Alerts whose time has passed are left out before this, so one that falls due while
+    // the jobs are being set up goes out straight away.
+    let time_to_sleep =
+        Duration::milliseconds(game_alert.time_to_alert - Utc::now().timestamp_millis())
+            .to_std()
+            .unwrap_or_default();
 
     let when = native_time::Instant::now() + time_to_sleep;
 
@@ -171,57 +440,388 @@ fn create_alert_job(
     Box::new(delayed_twilio_future)
 }
 
-fn record_parsed_games(games: &[game_parser::Game]) {
-    let game_statuses: Vec<dashboard::GameInfo> = games
+/// What the dashboard shows, shared by the alert jobs so that each records how its text
+/// went as soon as it has gone out.
+struct Dashboard {
+    games: Vec<game_parser::Game>,
+    /// The ID and text of each homestand heads-up.
+    heads_ups: Vec<(game_parser::GameId, String)>,
+    statuses: HashMap<game_parser::GameId, dashboard::WarningStatus>,
+    provenance: HashMap<game_parser::GameId, String>,
+    /// How many of each game's texts have yet to go out.
+    pending: HashMap<game_parser::GameId, usize>,
+}
+
+impl Dashboard {
+    /// A game with texts to send starts out Waiting, whatever an earlier run left it as.
+    fn new(
+        games: Vec<game_parser::Game>,
+        heads_ups: Vec<(game_parser::GameId, String)>,
+        mut statuses: HashMap<game_parser::GameId, dashboard::WarningStatus>,
+        provenance: HashMap<game_parser::GameId, String>,
+        alerts: &[GameAlert],
+    ) -> Dashboard {
+        let mut pending = HashMap::new();
+        for game_id in alerts.iter().filter_map(|alert| alert.game_id.as_ref()) {
+            *pending.entry(game_id.clone()).or_insert(0) += 1;
+            statuses.insert(game_id.clone(), dashboard::WarningStatus::Waiting);
+        }
+        Dashboard {
+            games,
+            heads_ups,
+            statuses,
+            provenance,
+            pending,
+        }
+    }
+
+    /// A game is an Error as soon as one of its texts fails, and Completed once the last
+    /// of them has gone out without one failing. The dashboard is rewritten every time.
+    fn record(&mut self, game_id: &game_parser::GameId, succeeded: bool) {
+        let remaining = self.pending.entry(game_id.clone()).or_insert(1);
+        *remaining = remaining.saturating_sub(1);
+        let finished = *remaining == 0;
+
+        let status = self
+            .statuses
+            .entry(game_id.clone())
+            .or_insert(dashboard::WarningStatus::Waiting);
+        if !succeeded {
+            *status = dashboard::WarningStatus::Error;
+        } else if finished && *status == dashboard::WarningStatus::Waiting {
+            *status = dashboard::WarningStatus::Completed;
+        }
+        self.write();
+    }
+
+    fn write(&self) {
+        record_parsed_games(
+            &self.games,
+            &self.heads_ups,
+            &self.statuses,
+            &self.provenance,
+        );
+    }
+}
+
+/// Writes the dashboard, keeping the status each game had by its ID so that it follows a
+/// game the schedule has moved. The heads-ups are listed after the games.
+fn record_parsed_games(
+    games: &[game_parser::Game],
+    heads_ups: &[(game_parser::GameId, String)],
+    statuses: &HashMap<game_parser::GameId, dashboard::WarningStatus>,
+    provenance: &HashMap<game_parser::GameId, String>,
+) {
+    let status_of = |id: Option<&game_parser::GameId>| {
+        id.and_then(|id| statuses.get(id))
+            .copied()
+            .unwrap_or(dashboard::WarningStatus::Waiting)
+    };
+    let game_statuses: Vec<dashboard::DashboardEntry> = games
         .iter()
-        .map(|game| dashboard::GameInfo {
-            game,
-            status: dashboard::WarningStatus::Waiting,
+        .map(|game| {
+            let id = game_parser::id::game_id(game);
+            dashboard::DashboardEntry::Game(dashboard::GameInfo {
+                status: status_of(id.as_ref()),
+                provenance: id.as_ref().and_then(|id| provenance.get(id)).cloned(),
+                id,
+                game,
+            })
         })
+        .chain(heads_ups.iter().map(|(id, purpose)| {
+            dashboard::DashboardEntry::Alert(dashboard::AlertInfo {
+                id: id.clone(),
+                purpose,
+                status: status_of(Some(id)),
+            })
+        }))
         .collect();
 
     let s = to_string(&game_statuses).expect("Could not seralize into string");
-    let mut file = File::create("foo.txt").expect("Could not open file to write");
+    let mut file = File::create(DASHBOARD_FILE).expect("Could not open file to write");
     file.write_all(s.as_bytes())
         .expect("Could not open write string to file")
 }
 
-fn parse_perfectly_scheduled_games(raw_csv_rows: Vec<Vec<&str>>) -> Vec<game_parser::Game> {
-    raw_csv_rows
-        .iter()
-        .filter_map(|row| match game_parser::parse_game_from(row.as_slice()) {
-            Some(game_parser::Game::PerfectlyScheduledGame { start_date_time }) => {
-                Some(game_parser::Game::PerfectlyScheduledGame { start_date_time })
+/// The statuses an earlier run left in the dashboard, by game ID.
+fn read_dashboard_statuses() -> HashMap<game_parser::GameId, dashboard::WarningStatus> {
+    match std::fs::read_to_string(DASHBOARD_FILE) {
+        Ok(json) => dashboard::read_statuses(&json).unwrap_or_else(|error| {
+            warn!("could not read {}: {}", DASHBOARD_FILE, error);
+            HashMap::new()
+        }),
+        Err(_) => HashMap::new(),
+    }
+}
+
+/// Keeps the games worth a text: scheduled games with a start time, and games on a known
+/// day whose time is still TBD. Every other game is logged with why it was left out.
+fn select_games(
+    schedule: Vec<game_parser::Game>,
+    venue_filter: &game_parser::filter::VenueFilter,
+    game_type_filter: &game_parser::filter::GameTypeFilter,
+) -> Vec<game_parser::Game> {
+    schedule
+        .into_iter()
+        .filter_map(|game| {
+            let status = game.details().status;
+            let status_problem = match (&game, status) {
+                (
+                    game_parser::Game::PerfectlyScheduledGame { .. },
+                    game_parser::GameStatus::Scheduled,
+                ) => None,
+                (_, game_parser::GameStatus::Scheduled) => {
+                    Some("its start time is unclear".to_string())
+                }
+                (_, game_parser::GameStatus::TimeTBD) => {
+                    match game_parser::status::game_day(&game) {
+                        Some(_) => None,
+                        None => Some("neither its day nor its time is known".to_string()),
+                    }
+                }
+                (_, status) => Some(format!("it was {}", status)),
+            };
+            let checked = match status_problem {
+                Some(reason) => Err(reason),
+                None => venue_filter
+                    .check(&game)
+                    .and_then(|()| game_type_filter.check(&game))
+                    .map_err(|reason| reason.to_string()),
+            };
+
+            match checked {
+                Ok(()) => Some(game),
+                Err(reason) => {
+                    info!("skipping {}: {}", describe_game(&game), reason);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// The sources in `sources`, highest priority last. Without that list the files in
+/// `schedules`, or just `schedule`, are read, after the Stats API when
+/// `stats_api_base_url` is set.
+fn get_sources(
+    settings: &Config,
+    resolve_options: &game_parser::resolve::ResolveOptions,
+) -> Vec<Box<dyn game_parser::source::EventSource>> {
+    let configs = optional(
+        "sources",
+        settings.get::<Vec<game_parser::source::SourceConfig>>("sources"),
+    )
+    .unwrap_or_else(|| {
+        let mut configs = vec![];
+        if let Ok(base_url) = settings.get_str("stats_api_base_url") {
+            configs.push(game_parser::source::SourceConfig {
+                kind: Some(game_parser::source::SourceKind::StatsApi),
+                base_url: Some(base_url),
+                team_id: settings.get_int("team_id").ok().map(|id| id as u32),
+                season: settings.get_int("season").ok().map(|season| season as i32),
+                ..Default::default()
+            });
+        }
+        configs.extend(get_schedule_files(settings).into_iter().map(|path| {
+            game_parser::source::SourceConfig {
+                path: Some(path),
+                ..Default::default()
             }
-            _ => None,
+        }));
+        configs
+    });
+
+    configs
+        .iter()
+        .map(|config| {
+            config
+                .source(resolve_options)
+                .unwrap_or_else(|error| panic!("{}", error))
         })
         .collect()
 }
 
-fn get_twilio_config() -> twilio::TwilioConfig {
+fn get_schedule_files(settings: &Config) -> Vec<String> {
+    optional("schedules", settings.get::<Vec<String>>("schedules")).unwrap_or_else(
+        || match settings.get_str("schedule") {
+            Ok(schedule) => vec![schedule],
+            Err(_) if settings.get_str("stats_api_base_url").is_ok() => vec![],
+            Err(_) => vec!["seattle-mariners-home-schedule.csv".to_string()],
+        },
+    )
+}
+
+/// Fetches every source and merges their games, later sources winning where they
+/// disagree. A source that can't be fetched, such as the Stats API while it is down, is
+/// logged and left out, and only when none can be is there nothing to go on. Alongside the
+/// games comes where each game's fields came from, by game ID, for the dashboard. Games are
+/// put through `Registry::normalise` before anything is worked out from them, so that
+/// their IDs and homestands don't depend on which names a schedule used.
+fn load_schedules(
+    sources: &[Box<dyn game_parser::source::EventSource>],
+    registry: &game_parser::registry::Registry,
+) -> (Vec<game_parser::Game>, HashMap<game_parser::GameId, String>) {
+    let schedules = sources
+        .iter()
+        .filter_map(|source| match fetch_games(source.as_ref()) {
+            Ok(games) => Some((source.name().to_string(), games)),
+            Err(error) => {
+                warn!("skipping {}: {}", source.name(), error);
+                None
+            }
+        })
+        .collect::<Vec<(String, Vec<game_parser::Game>)>>();
+    if schedules.is_empty() && !sources.is_empty() {
+        panic!("could not load any of the schedules");
+    }
+
+    let mut merged = game_parser::merge::merge_sources(&schedules);
+    for merged in merged.iter_mut() {
+        registry.normalise(&mut merged.game);
+    }
+    let provenance = merged
+        .iter()
+        .filter_map(|merged| {
+            let id = game_parser::id::game_id(&merged.game)?;
+            Some((id, merged.provenance.to_string()))
+        })
+        .collect();
+    let games = merged.into_iter().map(|merged| merged.game).collect();
+    (games, provenance)
+}
+
+/// The games of `source`, reporting anything wrong with its schedule so it can be fixed.
+fn fetch_games(
+    source: &dyn game_parser::source::EventSource,
+) -> Result<Vec<game_parser::Game>, game_parser::SourceError> {
+    let events = source.fetch()?;
+    for problem in &events.problems {
+        warn!("{}: {}", source.name(), problem);
+    }
+    Ok(events.games)
+}
+
+fn read_settings() -> Config {
     let mut settings = Config::default();
     settings.merge(ConfigFile::with_name("config")).expect(
         "you must supply a config file named config.toml matching config.template.toml's structure",
     );
-    let app_config = settings.try_into::<HashMap<String, String>>().expect(
-        "you must supply a config file named config.toml matching config.template.toml's structure",
-    );
+    settings
+}
+
+/// config.toml when there is one, for the commands that can do without it.
+fn read_optional_settings() -> Config {
+    let mut settings = Config::default();
+    settings
+        .merge(ConfigFile::with_name("config").required(false))
+        .expect("config.toml does not match config.template.toml's structure");
+    settings
+}
 
-    let from = app_config.get("from").expect("config.toml must define a from phone number in the form \"\\d\\d\\d\\d\\d\\d\\d\\d\\d\\d\"");
-    let to = app_config.get("to").expect(
+/// `None` when `key` is not set, so the caller can fall back to a default. A value that is
+/// set but can't be read is a mistake in config.toml, which would otherwise go unnoticed
+/// behind the default.
+fn optional<T>(key: &str, value: Result<T, ConfigError>) -> Option<T> {
+    match value {
+        Ok(value) => Some(value),
+        Err(ConfigError::NotFound(_)) => None,
+        Err(error) => panic!("config.toml's {} is not valid: {}", key, error),
+    }
+}
+
+fn get_twilio_config(settings: &Config) -> twilio::TwilioConfig {
+    let from = settings.get_str("from").expect("config.toml must define a from phone number in the form \"\\d\\d\\d\\d\\d\\d\\d\\d\\d\\d\"");
+    let to = settings.get_str("to").expect(
         "config.toml must define a to phone number in the form \"\\d\\d\\d\\d\\d\\d\\d\\d\\d\\d\"",
     );
-    let twilio_account_id = app_config
-        .get("twilio_account_id")
+    let twilio_account_id = settings
+        .get_str("twilio_account_id")
         .expect("config.toml must define a twilio_account_id");
-    let twilio_access_token = app_config
-        .get("twilio_access_token")
+    let twilio_access_token = settings
+        .get_str("twilio_access_token")
         .expect("config.toml must define a twilio_access_token");
 
     twilio::TwilioConfig {
-        from: from.clone(),
-        to: to.clone(),
-        twilio_access_token: twilio_access_token.clone(),
-        twilio_account_id: twilio_account_id.clone(),
+        from,
+        to,
+        twilio_access_token,
+        twilio_account_id,
+    }
+}
+
+/// Without a `default_first_pitch`, games whose time is TBD get an all-day warning instead.
+fn get_resolve_options(settings: &Config) -> game_parser::resolve::ResolveOptions {
+    let default_first_pitch = settings.get_str("default_first_pitch").ok().map(|time| {
+        NaiveTime::parse_from_str(time.trim(), game_parser::columns::TIME_FORMAT).unwrap_or_else(
+            |_| {
+                panic!(
+                    "default_first_pitch {:?} is not a time like \"07:10 PM\"",
+                    time
+                )
+            },
+        )
+    });
+    let date_formats = optional(
+        "date_formats",
+        settings.get::<game_parser::dates::DateFormats>("date_formats"),
+    )
+    .unwrap_or_default();
+    game_parser::resolve::ResolveOptions {
+        default_first_pitch,
+        date_formats,
     }
 }
+
+/// The teams and venues in `teams` and `venues`, or the Mariners, Seahawks and Sounders
+/// when there are none.
+fn get_registry(settings: &Config) -> game_parser::registry::Registry {
+    match optional(
+        "teams",
+        settings.get::<Vec<game_parser::registry::Team>>("teams"),
+    ) {
+        Some(teams) => game_parser::registry::Registry::new(
+            teams,
+            optional(
+                "venues",
+                settings.get::<Vec<game_parser::registry::Venue>>("venues"),
+            )
+            .unwrap_or_default(),
+        ),
+        None => game_parser::registry::Registry::sodo(),
+    }
+}
+
+/// Games elsewhere don't flood SODO; `near_venues` defaults to every venue in the registry
+/// and each team's home venue. Names the registry knows also match its name for them,
+/// which is what games are called by after `Registry::normalise`.
+fn get_venue_filter(
+    settings: &Config,
+    registry: &game_parser::registry::Registry,
+) -> game_parser::filter::VenueFilter {
+    let near_venues = optional("near_venues", settings.get::<Vec<String>>("near_venues"))
+        .unwrap_or_else(|| {
+            registry
+                .venues()
+                .iter()
+                .flat_map(|venue| std::iter::once(&venue.name).chain(&venue.aliases))
+                .chain(registry.teams().iter().map(|team| &team.venue))
+                .cloned()
+                .collect()
+        });
+    game_parser::filter::VenueFilter::with_registry(&near_venues, registry)
+}
+
+/// Spring training is left out unless `game_types` asks for it.
+fn get_game_type_filter(settings: &Config) -> game_parser::filter::GameTypeFilter {
+    let game_types = optional(
+        "game_types",
+        settings.get::<Vec<game_parser::GameType>>("game_types"),
+    )
+    .unwrap_or_else(|| {
+        vec![
+            game_parser::GameType::RegularSeason,
+            game_parser::GameType::Postseason,
+        ]
+    });
+    game_parser::filter::GameTypeFilter::new(&game_types)
+}